@@ -0,0 +1,107 @@
+//! Derive macros for [`tower-sessions-seaorm-store`](https://crates.io/crates/tower-sessions-seaorm-store).
+//!
+//! These macros are re-exported by `tower-sessions-seaorm-store` behind its `axum` feature and
+//! should be used through that crate rather than depended on directly.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Field, Fields, Index, Member, Type};
+
+/// The names of the store type and its aliases the derive looks for.
+const STORE_TYPE_NAMES: &[&str] = &["PostgresStore", "SeaOrmStore", "SqliteStore"];
+
+/// The attribute marking the store field when its type isn't named after the store.
+const STORE_ATTRIBUTE: &str = "session_store";
+
+/// Derives `axum::extract::FromRef<State>` for the `PostgresStore` held by a state struct.
+///
+/// See the documentation of `tower_sessions_seaorm_store::SessionStoreFromRef` for details.
+#[proc_macro_derive(SessionStoreFromRef, attributes(session_store))]
+pub fn derive_session_store_from_ref(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "SessionStoreFromRef can only be derived for structs",
+        ));
+    };
+
+    // Find the field holding the store, by its attribute or the last segment of its type path
+    let marked = data.fields.iter().any(is_marked);
+    let mut store_fields = store_fields(&data.fields, marked);
+    let (member, store_type) = match (store_fields.next(), store_fields.next()) {
+        (Some(field), None) => field,
+        (None, _) => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                format!(
+                    "SessionStoreFromRef requires a field of type `{}`, or one marked `#[{STORE_ATTRIBUTE}]`",
+                    STORE_TYPE_NAMES.join("`, `"),
+                ),
+            ))
+        }
+        (Some(_), Some((_, second))) => {
+            return Err(syn::Error::new(
+                second.span(),
+                if marked {
+                    format!("SessionStoreFromRef requires exactly one field marked `#[{STORE_ATTRIBUTE}]`")
+                } else {
+                    format!(
+                        "SessionStoreFromRef requires exactly one store field, mark the one to extract with `#[{STORE_ATTRIBUTE}]`"
+                    )
+                },
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::tower_sessions_seaorm_store::__private::FromRef<#name #ty_generics>
+            for #store_type #where_clause
+        {
+            fn from_ref(state: &#name #ty_generics) -> Self {
+                ::core::clone::Clone::clone(&state.#member)
+            }
+        }
+    })
+}
+
+/// Returns the members and types of the fields marked `#[session_store]` if `marked`, and
+/// otherwise of the fields whose type is named after the store.
+fn store_fields(fields: &Fields, marked: bool) -> impl Iterator<Item = (Member, &Type)> {
+    fields
+        .iter()
+        .enumerate()
+        .filter(move |(_, field)| if marked { is_marked(field) } else { is_store_type(&field.ty) })
+        .map(|(index, field)| {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(Index::from(index)),
+            };
+            (member, &field.ty)
+        })
+}
+
+fn is_marked(field: &Field) -> bool {
+    field.attrs.iter().any(|attr| attr.path().is_ident(STORE_ATTRIBUTE))
+}
+
+fn is_store_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| STORE_TYPE_NAMES.iter().any(|name| segment.ident == name)),
+        _ => false,
+    }
+}
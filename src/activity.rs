@@ -0,0 +1,150 @@
+//! Hooks into session activity for analytics.
+//!
+//! A [`SessionActivityTracker`] installed with `PostgresStore::with_session_activity_tracker`
+//! is notified after every successful operation that creates, loads or deletes sessions. Unlike
+//! a [`TelemetryProvider`](crate::TelemetryProvider), which measures the store itself, it
+//! receives the IDs of the sessions involved.
+
+use std::fmt;
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use tower_sessions::session::Id;
+
+/// Receives the session activity of a store.
+///
+/// Hooks are called inline once the operation has succeeded, so they should be cheap and must
+/// not block. Offload slow work, e.g. with [`ChannelActivityTracker`].
+pub trait SessionActivityTracker: Send + Sync + 'static {
+    /// Called after `load()`, with whether an active session was found for `id`.
+    fn on_session_loaded(&self, id: &Id, found: bool);
+
+    /// Called after `create()` stored a new session, with its final ID.
+    fn on_session_created(&self, id: &Id);
+
+    /// Called after `delete()`, whether or not the session existed.
+    fn on_session_deleted(&self, id: &Id);
+
+    /// Called after `delete_expired()` with the number of deleted sessions.
+    fn on_session_expired(&self, count: u64);
+}
+
+/// A [`SessionActivityTracker`] that emits a `tracing` event at `INFO` level for every event.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConsolePrintActivityTracker;
+
+impl SessionActivityTracker for ConsolePrintActivityTracker {
+    fn on_session_loaded(&self, id: &Id, found: bool) {
+        tracing::info!(session_id = %id, found, "session loaded");
+    }
+
+    fn on_session_created(&self, id: &Id) {
+        tracing::info!(session_id = %id, "session created");
+    }
+
+    fn on_session_deleted(&self, id: &Id) {
+        tracing::info!(session_id = %id, "session deleted");
+    }
+
+    fn on_session_expired(&self, count: u64) {
+        tracing::info!(count, "expired sessions deleted");
+    }
+}
+
+/// A session activity event, as forwarded by [`ChannelActivityTracker`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActivityEvent {
+    /// A session was loaded; `found` is `false` if no active session had the ID.
+    Loaded {
+        /// The ID passed to `load()`.
+        id: Id,
+        /// Whether an active session was found.
+        found: bool,
+    },
+    /// A session was created.
+    Created {
+        /// The ID of the new session.
+        id: Id,
+    },
+    /// A session was deleted.
+    Deleted {
+        /// The ID passed to `delete()`.
+        id: Id,
+    },
+    /// Expired sessions were deleted.
+    Expired {
+        /// The number of deleted sessions.
+        count: u64,
+    },
+}
+
+/// A [`SessionActivityTracker`] forwarding every event to an async channel.
+///
+/// Events are sent with `try_send`, so the store never waits for the receiver: events are
+/// dropped when the channel is full or closed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use tokio::sync::mpsc;
+/// use tower_sessions_seaorm_store::{ChannelActivityTracker, PostgresStore};
+///
+/// # fn example(store: PostgresStore) {
+/// let (sender, mut receiver) = mpsc::channel(1024);
+/// let store = store.with_session_activity_tracker(Arc::new(ChannelActivityTracker(sender)));
+///
+/// tokio::spawn(async move {
+///     while let Some(event) = receiver.recv().await {
+///         println!("{event:?}");
+///     }
+/// });
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChannelActivityTracker(pub mpsc::Sender<ActivityEvent>);
+
+impl ChannelActivityTracker {
+    fn send(&self, event: ActivityEvent) {
+        // Dropping events is preferable to slowing down the store
+        let _ = self.0.try_send(event);
+    }
+}
+
+impl SessionActivityTracker for ChannelActivityTracker {
+    fn on_session_loaded(&self, id: &Id, found: bool) {
+        self.send(ActivityEvent::Loaded { id: *id, found });
+    }
+
+    fn on_session_created(&self, id: &Id) {
+        self.send(ActivityEvent::Created { id: *id });
+    }
+
+    fn on_session_deleted(&self, id: &Id) {
+        self.send(ActivityEvent::Deleted { id: *id });
+    }
+
+    fn on_session_expired(&self, count: u64) {
+        self.send(ActivityEvent::Expired { count });
+    }
+}
+
+/// A shared activity tracker, cloned along with the store.
+#[derive(Clone)]
+pub(crate) struct ActivityTracking(Arc<dyn SessionActivityTracker>);
+
+impl ActivityTracking {
+    pub(crate) fn new(tracker: Arc<dyn SessionActivityTracker>) -> Self {
+        Self(tracker)
+    }
+
+    pub(crate) fn tracker(&self) -> &dyn SessionActivityTracker {
+        self.0.as_ref()
+    }
+}
+
+impl fmt::Debug for ActivityTracking {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ActivityTracking").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,88 @@
+//! Audit trail of session operations.
+
+use std::collections::BTreeMap;
+
+use sea_orm::sea_query::{Alias, Expr, InsertStatement, IntoTableRef, Query, SelectStatement, TableRef};
+use sea_orm::DbBackend;
+use sha2::{Digest, Sha256};
+use tower_sessions::session::Record;
+
+use crate::operation::StoreOperation;
+
+/// Name of the table holding the audit trail.
+pub(crate) const AUDIT_LOG_TABLE: &str = "session_audit_log";
+
+/// Returns the hex-encoded SHA-256 hash of the MessagePack-encoded `record`.
+///
+/// Session keys are encoded in sorted order, so equal records always have the same hash.
+pub(crate) fn data_hash(record: &Record) -> Result<String, crate::SeaOrmStoreError> {
+    let data: BTreeMap<_, _> = record.data.iter().collect();
+    // Encoded like `Record`, which is a struct of the same three fields
+    let encoded = rmp_serde::to_vec(&(&record.id, data, &record.expiry_date))?;
+
+    Ok(format!("{:x}", Sha256::digest(encoded)))
+}
+
+/// Builds the statement appending an entry to the audit log on `backend`.
+///
+/// `session_id` is the primary key of the session row, so the log holds the same hashes as the
+/// session table when session IDs are hashed.
+pub(crate) fn insert_statement(
+    backend: DbBackend,
+    schema: &str,
+    op: StoreOperation,
+    session_id: &str,
+    actor: Option<&str>,
+    data_hash: Option<String>,
+) -> InsertStatement {
+    Query::insert()
+        .into_table(table_ref(backend, schema))
+        .columns([
+            Alias::new("session_id"),
+            Alias::new("operation"),
+            Alias::new("actor"),
+            Alias::new("data_hash"),
+        ])
+        .values_panic([
+            session_id.into(),
+            op.as_str().into(),
+            actor.map(str::to_owned).into(),
+            data_hash.into(),
+        ])
+        .to_owned()
+}
+
+/// Builds the statement appending an entry for `op` to the audit log on `backend` for every
+/// session selected by `sessions`, a query of the `id` column of the session table.
+///
+/// Bulk deletions remove many sessions at once, so the entries are written by one
+/// `INSERT ... SELECT` instead of a statement per session, without data hashes.
+pub(crate) fn insert_all_statement(
+    backend: DbBackend,
+    schema: &str,
+    op: StoreOperation,
+    mut sessions: SelectStatement,
+    actor: Option<&str>,
+) -> InsertStatement {
+    sessions
+        .expr(Expr::val(op.as_str()))
+        .expr(Expr::val(actor.map(str::to_owned)));
+
+    Query::insert()
+        .into_table(table_ref(backend, schema))
+        .columns([Alias::new("session_id"), Alias::new("operation"), Alias::new("actor")])
+        .select_from(sessions)
+        .expect("the selection has a value for every column")
+        .to_owned()
+}
+
+/// Returns the audit log table on `backend`.
+///
+/// Like the session table, the audit log is only schema-qualified on PostgreSQL, where the
+/// migration creates it in the schema of the session table.
+fn table_ref(backend: DbBackend, schema: &str) -> TableRef {
+    match backend {
+        DbBackend::Postgres => (Alias::new(schema), Alias::new(AUDIT_LOG_TABLE)).into_table_ref(),
+        DbBackend::MySql | DbBackend::Sqlite => Alias::new(AUDIT_LOG_TABLE).into_table_ref(),
+    }
+}
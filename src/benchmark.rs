@@ -0,0 +1,120 @@
+//! Built-in load benchmark of the session store.
+//!
+//! `PostgresStore::benchmark_suite` runs create/load/save/delete cycles from several concurrent
+//! clients and reports latency percentiles per operation. This makes it possible to compare
+//! PostgreSQL hosting options without setting up a full application.
+
+use std::time::{Duration, Instant};
+
+use tower_sessions::session::Id;
+use tower_sessions::session_store;
+
+use crate::operation::StoreOperation;
+
+/// Parameters of a benchmark run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchmarkConfig {
+    /// Number of clients running cycles concurrently, each in its own task.
+    pub concurrent_clients: u32,
+    /// Number of create/load/save/delete cycles run by each client.
+    pub iterations_per_client: u32,
+}
+
+/// Latency percentiles measured by a benchmark run, in milliseconds.
+///
+/// Only successful operations are measured; failed ones are counted in `total_errors`.
+/// Percentiles of an operation that never succeeded are `0.0`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BenchmarkResults {
+    /// Median latency of `SessionStore::create`.
+    pub create_p50_ms: f64,
+    /// 99th percentile latency of `SessionStore::create`.
+    pub create_p99_ms: f64,
+    /// Median latency of `SessionStore::load`.
+    pub load_p50_ms: f64,
+    /// 99th percentile latency of `SessionStore::load`.
+    pub load_p99_ms: f64,
+    /// Median latency of `SessionStore::save`.
+    pub save_p50_ms: f64,
+    /// 99th percentile latency of `SessionStore::save`.
+    pub save_p99_ms: f64,
+    /// Median latency of `SessionStore::delete`.
+    pub delete_p50_ms: f64,
+    /// 99th percentile latency of `SessionStore::delete`.
+    pub delete_p99_ms: f64,
+    /// Number of operations that failed.
+    pub total_errors: u64,
+}
+
+/// Measurements collected by the benchmark clients.
+#[derive(Debug, Default)]
+pub(crate) struct BenchmarkSamples {
+    create: Vec<Duration>,
+    load: Vec<Duration>,
+    save: Vec<Duration>,
+    delete: Vec<Duration>,
+    errors: u64,
+    /// Sessions that may still exist because their cycle didn't complete.
+    pub(crate) leftover_sessions: Vec<Id>,
+}
+
+impl BenchmarkSamples {
+    /// Records the outcome of `op`, started at `started_at`.
+    pub(crate) fn record<T>(
+        &mut self,
+        op: StoreOperation,
+        started_at: Instant,
+        result: &session_store::Result<T>,
+    ) {
+        let elapsed = started_at.elapsed();
+        if result.is_err() {
+            self.errors += 1;
+            return;
+        }
+
+        match op {
+            StoreOperation::Create => self.create.push(elapsed),
+            StoreOperation::Load => self.load.push(elapsed),
+            StoreOperation::Save => self.save.push(elapsed),
+            StoreOperation::Delete => self.delete.push(elapsed),
+            StoreOperation::DeleteExpired => {}
+        }
+    }
+
+    /// Adds the measurements of another client.
+    pub(crate) fn merge(&mut self, other: BenchmarkSamples) {
+        self.create.extend(other.create);
+        self.load.extend(other.load);
+        self.save.extend(other.save);
+        self.delete.extend(other.delete);
+        self.errors += other.errors;
+        self.leftover_sessions.extend(other.leftover_sessions);
+    }
+
+    /// Computes the latency percentiles of the collected measurements.
+    pub(crate) fn results(mut self) -> BenchmarkResults {
+        BenchmarkResults {
+            create_p50_ms: percentile_ms(&mut self.create, 50.0),
+            create_p99_ms: percentile_ms(&mut self.create, 99.0),
+            load_p50_ms: percentile_ms(&mut self.load, 50.0),
+            load_p99_ms: percentile_ms(&mut self.load, 99.0),
+            save_p50_ms: percentile_ms(&mut self.save, 50.0),
+            save_p99_ms: percentile_ms(&mut self.save, 99.0),
+            delete_p50_ms: percentile_ms(&mut self.delete, 50.0),
+            delete_p99_ms: percentile_ms(&mut self.delete, 99.0),
+            total_errors: self.errors,
+        }
+    }
+}
+
+/// Returns the `percentile`th duration of `samples` in milliseconds, using the nearest-rank
+/// method, or `0.0` if there are no samples.
+fn percentile_ms(samples: &mut [Duration], percentile: f64) -> f64 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+
+    samples.sort_unstable();
+    let rank = (percentile / 100.0 * samples.len() as f64).ceil() as usize;
+    samples[rank.clamp(1, samples.len()) - 1].as_secs_f64() * 1000.0
+}
@@ -0,0 +1,108 @@
+//! `sessions`: administration of the session table of a `tower-sessions-seaorm-store` store.
+//!
+//! Built with the `cli` feature:
+//!
+//! ```bash
+//! cargo install tower-sessions-seaorm-store --features cli
+//! sessions --database-url postgres://localhost/app count
+//! ```
+
+use std::error::Error;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+use clap::{Parser, Subcommand};
+use sea_orm::Database;
+use time::format_description::well_known::Rfc3339;
+use tower_sessions::session::Id;
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::PostgresStore;
+
+/// Administration of the session table of a tower-sessions-seaorm-store store.
+#[derive(Parser)]
+#[command(name = "sessions", version)]
+struct Cli {
+    /// URL of the database holding the sessions.
+    #[arg(long, env = "DATABASE_URL")]
+    database_url: String,
+
+    /// Schema of the session table, on PostgreSQL.
+    #[arg(long, default_value = "tower_sessions")]
+    schema: String,
+
+    /// Name of the session table.
+    #[arg(long, default_value = "session")]
+    table: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Creates or updates the session table.
+    Migrate,
+    /// Prints the number of active and expired sessions.
+    Count,
+    /// Deletes the expired sessions and prints how many were deleted.
+    PurgeExpired,
+    /// Prints an active session as JSON.
+    Inspect {
+        /// The ID of the session, as stored in its cookie.
+        id: String,
+    },
+    /// Deletes a session.
+    Delete {
+        /// The ID of the session, as stored in its cookie.
+        id: String,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<ExitCode, Box<dyn Error>> {
+    let cli = Cli::parse();
+
+    let conn = Database::connect(&cli.database_url).await?;
+    let store = PostgresStore::new(conn)
+        .with_schema_name(cli.schema)?
+        .with_table_name(cli.table)?;
+
+    match cli.command {
+        Command::Migrate => {
+            store.migrate().await?;
+            println!("session table migrated");
+        }
+        Command::Count => {
+            println!("active: {}", store.count_active().await?);
+            println!("expired: {}", store.count_expired().await?);
+        }
+        Command::PurgeExpired => {
+            println!("deleted: {}", store.delete_expired_count().await?);
+        }
+        Command::Inspect { id } => {
+            let Some(record) = store.load(&parse_id(&id)?).await? else {
+                eprintln!("session {id} not found or expired");
+                return Ok(ExitCode::FAILURE);
+            };
+
+            let session = serde_json::json!({
+                "id": record.id.to_string(),
+                "expiry_date": record.expiry_date.format(&Rfc3339)?,
+                "data": record.data,
+            });
+            println!("{}", serde_json::to_string_pretty(&session)?);
+        }
+        Command::Delete { id } => {
+            store.delete(&parse_id(&id)?).await?;
+            println!("session {id} deleted");
+        }
+    }
+
+    store.close().await?;
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Parses a session ID as found in a session cookie.
+fn parse_id(id: &str) -> Result<Id, Box<dyn Error>> {
+    Id::from_str(id).map_err(|err| format!("invalid session id {id:?}: {err}").into())
+}
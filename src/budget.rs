@@ -0,0 +1,106 @@
+//! Process-wide budget of database connections shared by the stores that own their pool.
+//!
+//! Stores created with `PostgresStore::from_url_with_pool_config` reserve their maximum pool
+//! size from the budget installed with [`GlobalConnectionBudget::new`], and return it once
+//! the last clone of the store is dropped. Without an installed budget, nothing is reserved.
+
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Number of connections left in the process-wide budget, once installed.
+static BUDGET: OnceLock<Arc<Mutex<u32>>> = OnceLock::new();
+
+/// A process-wide limit on the connections opened by session stores.
+///
+/// When several stores own their connection pool, for example one store per tenant, their
+/// pools together can exceed the `max_connections` setting of PostgreSQL. Installing a budget
+/// makes `PostgresStore::from_url_with_pool_config` reserve the maximum size of each new pool
+/// from the budget, and fail once the budget is used up. Connections are returned to the
+/// budget when the last clone of a store is dropped.
+///
+/// Stores created with `PostgresStore::new` use a connection managed by the application and
+/// are not counted.
+#[derive(Debug, Clone)]
+pub struct GlobalConnectionBudget {
+    remaining: Arc<Mutex<u32>>,
+}
+
+impl GlobalConnectionBudget {
+    /// Installs a process-wide budget of `total` connections.
+    ///
+    /// The budget can be installed only once per process.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SeaOrmStoreError::ConnectionBudgetAlreadyInstalled`](crate::SeaOrmStoreError::ConnectionBudgetAlreadyInstalled)
+    /// if a budget was installed before.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tower_sessions_seaorm_store::GlobalConnectionBudget;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// // Keep session stores below the server's max_connections of 100
+    /// let budget = GlobalConnectionBudget::new(80)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(total: u32) -> Result<Self, crate::SeaOrmStoreError> {
+        let remaining = Arc::new(Mutex::new(total));
+        BUDGET
+            .set(remaining.clone())
+            .map_err(|_| crate::SeaOrmStoreError::ConnectionBudgetAlreadyInstalled)?;
+
+        Ok(Self { remaining })
+    }
+
+    /// Returns the installed budget, if any.
+    pub fn installed() -> Option<Self> {
+        BUDGET.get().map(|remaining| Self {
+            remaining: remaining.clone(),
+        })
+    }
+
+    /// Returns the number of connections that can still be reserved.
+    pub fn remaining(&self) -> u32 {
+        *self.remaining.lock().expect("connection budget poisoned")
+    }
+}
+
+/// Connections reserved from the installed budget, returned when dropped.
+#[derive(Debug)]
+pub(crate) struct BudgetReservation {
+    remaining: Arc<Mutex<u32>>,
+    connections: u32,
+}
+
+impl BudgetReservation {
+    /// Reserves `connections` from the installed budget.
+    ///
+    /// Returns `Ok(None)` when no budget is installed.
+    pub(crate) fn reserve(connections: u32) -> Result<Option<Self>, crate::SeaOrmStoreError> {
+        let Some(remaining) = BUDGET.get() else {
+            return Ok(None);
+        };
+
+        let mut available = remaining.lock().expect("connection budget poisoned");
+        if connections > *available {
+            return Err(crate::SeaOrmStoreError::ConnectionBudgetExceeded {
+                requested: connections,
+                available: *available,
+            });
+        }
+        *available -= connections;
+
+        Ok(Some(Self {
+            remaining: remaining.clone(),
+            connections,
+        }))
+    }
+}
+
+impl Drop for BudgetReservation {
+    fn drop(&mut self) {
+        *self.remaining.lock().expect("connection budget poisoned") += self.connections;
+    }
+}
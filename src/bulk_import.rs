@@ -0,0 +1,92 @@
+//! CSV rows streamed to PostgreSQL by `PostgresStore::bulk_import` with `COPY ... FROM STDIN`.
+//!
+//! Every row holds the columns of [`session::Column`] in declaration order, followed by the
+//! tenant ID of a store scoped to a tenant. Text is quoted, `BYTEA` data is written in hex
+//! format, and `NULL` is an empty unquoted field.
+
+use std::fmt::Write;
+
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::Iterable;
+
+use crate::data_format::SessionDataFormat;
+use crate::entity::session;
+
+/// Name of the temporary table sessions are copied into, before they are moved to the
+/// session table in a single statement.
+pub(crate) const STAGING_TABLE: &str = "tower_sessions_bulk_import";
+
+/// Size of the chunks of CSV sent to the server, in bytes.
+pub(crate) const CHUNK_SIZE: usize = 1 << 20;
+
+/// Appends the row of `model` to `csv`, with `tenant_id` as the last column if set.
+pub(crate) fn encode_row(
+    csv: &mut String,
+    model: &session::Model,
+    format: SessionDataFormat,
+    tenant_id: Option<&str>,
+) -> Result<(), crate::SeaOrmStoreError> {
+    for (index, column) in session::Column::iter().enumerate() {
+        if index > 0 {
+            csv.push(',');
+        }
+        match column {
+            session::Column::Id => push_text(csv, &model.id),
+            session::Column::Data => match format {
+                SessionDataFormat::MessagePack => {
+                    csv.push_str("\\x");
+                    for byte in &model.data {
+                        write!(csv, "{byte:02x}").expect("writing to a String can't fail");
+                    }
+                }
+                // The encoded data is JSON text, which PostgreSQL parses into JSONB
+                SessionDataFormat::Jsonb => push_text(
+                    csv,
+                    std::str::from_utf8(&model.data).map_err(|err| crate::SeaOrmStoreError::Encode(err.to_string()))?,
+                ),
+            },
+            session::Column::ExpiryDate => push_time(csv, model.expiry_date),
+            session::Column::LastAccessedAt => push_time(csv, model.last_accessed_at),
+            session::Column::Version => write!(csv, "{}", model.version).expect("writing to a String can't fail"),
+            session::Column::UserId => {
+                if let Some(user_id) = &model.user_id {
+                    push_text(csv, user_id);
+                }
+            }
+            session::Column::CreatedAt => {
+                if let Some(created_at) = model.created_at {
+                    push_time(csv, created_at);
+                }
+            }
+            session::Column::UpdatedAt => {
+                if let Some(updated_at) = model.updated_at {
+                    push_time(csv, updated_at);
+                }
+            }
+            session::Column::DataHash => {
+                if let Some(data_hash) = model.data_hash {
+                    write!(csv, "{data_hash}").expect("writing to a String can't fail");
+                }
+            }
+        }
+    }
+    if let Some(tenant_id) = tenant_id {
+        csv.push(',');
+        push_text(csv, tenant_id);
+    }
+    csv.push('\n');
+
+    Ok(())
+}
+
+/// Appends `text` as a quoted field, which is never read as `NULL`.
+fn push_text(csv: &mut String, text: &str) {
+    csv.push('"');
+    csv.push_str(&text.replace('"', "\"\""));
+    csv.push('"');
+}
+
+/// Appends `time` in RFC 3339 format, which PostgreSQL parses with its offset.
+fn push_time(csv: &mut String, time: DateTimeWithTimeZone) {
+    csv.push_str(&time.to_rfc3339());
+}
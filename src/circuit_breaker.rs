@@ -0,0 +1,136 @@
+//! Circuit breaker protecting the database from sustained failures.
+//!
+//! When enabled via `PostgresStore::with_circuit_breaker`, the circuit "opens" after a
+//! configurable number of consecutive database failures. While open, store operations fail
+//! immediately without touching the connection pool. A background task pings the database
+//! every probe interval and moves the circuit to half-open once the database responds again;
+//! enough consecutive successes in the half-open state close the circuit.
+
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+use sea_orm::DatabaseConnection;
+use tower_sessions::session_store;
+
+/// Configuration of the circuit breaker installed by `PostgresStore::with_circuit_breaker`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    /// Number of consecutive database failures that open the circuit.
+    pub failure_threshold: u32,
+    /// Number of consecutive successes in the half-open state that close the circuit again.
+    pub success_threshold: u32,
+    /// Time between two recovery probes while the circuit is open.
+    pub probe_interval: Duration,
+}
+
+/// The state of a circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Operations reach the database normally.
+    Closed {
+        /// Number of database failures in a row since the last success.
+        consecutive_failures: u32,
+    },
+    /// Operations fail immediately until a recovery probe succeeds.
+    Open,
+    /// The database answered a probe; operations are let through to confirm recovery.
+    HalfOpen {
+        /// Number of successful operations in a row since the circuit became half-open.
+        consecutive_successes: u32,
+    },
+}
+
+/// Shared circuit breaker state, cloned along with the store.
+#[derive(Debug, Clone)]
+pub(crate) struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: Arc<Mutex<CircuitState>>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: Arc::new(Mutex::new(CircuitState::Closed {
+                consecutive_failures: 0,
+            })),
+        }
+    }
+
+    /// Returns the current state of the circuit.
+    pub(crate) fn state(&self) -> CircuitState {
+        *self.state.lock().expect("circuit breaker state poisoned")
+    }
+
+    /// Returns `true` if operations may reach the database.
+    pub(crate) fn is_call_permitted(&self) -> bool {
+        self.state() != CircuitState::Open
+    }
+
+    /// Records the outcome of an operation and updates the circuit accordingly.
+    ///
+    /// Only backend errors count as failures: encoding and decoding errors mean the database
+    /// itself answered. When the circuit opens, a recovery probe is spawned on `conn`.
+    pub(crate) fn record<T>(&self, result: &session_store::Result<T>, conn: &DatabaseConnection) {
+        let failed = matches!(result, Err(session_store::Error::Backend(_)));
+        let mut state = self.state.lock().expect("circuit breaker state poisoned");
+
+        *state = match (*state, failed) {
+            (CircuitState::Closed { .. }, false) => CircuitState::Closed {
+                consecutive_failures: 0,
+            },
+            (CircuitState::Closed { consecutive_failures }, true) => {
+                if consecutive_failures + 1 >= self.config.failure_threshold {
+                    self.spawn_probe(conn.clone());
+                    CircuitState::Open
+                } else {
+                    CircuitState::Closed {
+                        consecutive_failures: consecutive_failures + 1,
+                    }
+                }
+            }
+            (CircuitState::HalfOpen { consecutive_successes }, false) => {
+                if consecutive_successes + 1 >= self.config.success_threshold {
+                    CircuitState::Closed {
+                        consecutive_failures: 0,
+                    }
+                } else {
+                    CircuitState::HalfOpen {
+                        consecutive_successes: consecutive_successes + 1,
+                    }
+                }
+            }
+            (CircuitState::HalfOpen { .. }, true) => {
+                self.spawn_probe(conn.clone());
+                CircuitState::Open
+            }
+            // Operations that started before the circuit opened don't change anything
+            (CircuitState::Open, _) => CircuitState::Open,
+        };
+    }
+
+    /// Spawns a task that pings the database until it answers, then half-opens the circuit.
+    fn spawn_probe(&self, conn: DatabaseConnection) {
+        let state: Weak<Mutex<CircuitState>> = Arc::downgrade(&self.state);
+        let probe_interval = self.config.probe_interval;
+
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(probe_interval).await;
+
+                // Stop probing once every store sharing this circuit has been dropped
+                let Some(state) = state.upgrade() else {
+                    return;
+                };
+
+                if conn.ping().await.is_ok() {
+                    *state.lock().expect("circuit breaker state poisoned") =
+                        CircuitState::HalfOpen {
+                            consecutive_successes: 0,
+                        };
+                    return;
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,93 @@
+//! Background deletion of expired sessions.
+//!
+//! `PostgresStore::spawn_cleanup` starts a task deleting expired sessions periodically. Every
+//! wait between two runs is the configured interval plus or minus a random jitter of up to a
+//! tenth of it, so instances started together drift apart instead of sweeping the table at the
+//! same time.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures_util::future::{self, Either};
+use rand::Rng;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+use crate::PostgresStore;
+
+/// Handle to the cleanup task started by `PostgresStore::spawn_cleanup`.
+///
+/// Dropping the handle detaches the task, which then runs until the runtime shuts down. Call
+/// [`CleanupHandle::shutdown`] to stop it gracefully.
+#[derive(Debug)]
+pub struct CleanupHandle {
+    shutdown: Arc<Notify>,
+    task: JoinHandle<()>,
+}
+
+impl CleanupHandle {
+    /// Stops the cleanup task and waits for it to finish.
+    ///
+    /// A run in progress is completed first, so no delete statement is interrupted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SeaOrmStoreError::Task`](crate::SeaOrmStoreError::Task) if the task panicked.
+    pub async fn shutdown(self) -> Result<(), crate::SeaOrmStoreError> {
+        // A stored permit is picked up even if a run is in progress
+        self.shutdown.notify_one();
+        Ok(self.task.await?)
+    }
+
+    /// Returns `true` if the cleanup task has stopped.
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+}
+
+/// Spawns the task deleting the expired sessions of `store` about every `interval`.
+///
+/// `interval` is also recorded in `cleanup_interval` while the task runs, which is shared
+/// with the store so that `gc_policy()` reports it.
+pub(crate) fn spawn(
+    store: PostgresStore,
+    interval: Duration,
+    cleanup_interval: Arc<Mutex<Option<Duration>>>,
+) -> CleanupHandle {
+    let shutdown = Arc::new(Notify::new());
+    let stop = shutdown.clone();
+
+    *cleanup_interval.lock().expect("cleanup interval poisoned") = Some(interval);
+    let task = tokio::spawn(async move {
+        tracing::debug!(interval_ms = interval.as_millis(), "expired session cleanup started");
+        loop {
+            let wait = tokio::time::sleep(jittered(interval));
+            match future::select(Box::pin(wait), Box::pin(stop.notified())).await {
+                Either::Left(_) => {}
+                Either::Right(_) => break,
+            }
+
+            if store.is_closed() {
+                break;
+            }
+            // Successful runs are logged by the store with the number of deleted sessions
+            if let Err(err) = store.run_cleanup().await {
+                tracing::warn!(error = %err, "expired session cleanup failed");
+            }
+        }
+        *cleanup_interval.lock().expect("cleanup interval poisoned") = None;
+        tracing::debug!("expired session cleanup stopped");
+    });
+
+    CleanupHandle { shutdown, task }
+}
+
+/// Returns `interval` with a random jitter of up to a tenth of it in either direction.
+fn jittered(interval: Duration) -> Duration {
+    let jitter = interval / 10;
+    if jitter.is_zero() {
+        return interval;
+    }
+
+    interval - jitter + rand::thread_rng().gen_range(Duration::ZERO..jitter * 2)
+}
@@ -0,0 +1,224 @@
+//! Capture of the IP address and user agent of the client a session is written for.
+//!
+//! [`ClientMetadataLayer`] reads both from the request and scopes them to the task handling
+//! it. The store writes them to the `ip_address` and `user_agent` columns whenever it writes a
+//! session within that scope, which covers the save `SessionManagerLayer` makes at the end of
+//! the request as long as the metadata layer wraps it. Writes made outside a request, such as
+//! by an admin tool, leave the columns of existing sessions untouched.
+
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use http::header::USER_AGENT;
+use http::{HeaderMap, Request};
+use tokio::task::futures::TaskLocalFuture;
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Longest user agent stored, in bytes; longer ones are truncated.
+const MAX_USER_AGENT_LEN: usize = 512;
+
+tokio::task_local! {
+    /// Client of the request handled by the current task.
+    static CURRENT: ClientMetadata;
+}
+
+/// The client a session is written for.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientMetadata {
+    /// The IP address of the client, if known.
+    pub ip_address: Option<IpAddr>,
+    /// The `User-Agent` header sent by the client, if any.
+    pub user_agent: Option<String>,
+}
+
+impl ClientMetadata {
+    /// Returns the client of the request handled by the current task, or `None` outside a
+    /// [`ClientMetadataLayer`].
+    pub fn current() -> Option<ClientMetadata> {
+        CURRENT.try_with(ClientMetadata::clone).ok()
+    }
+
+    /// Runs `fut` with `self` as the client of the sessions it writes.
+    ///
+    /// This is what [`ClientMetadataLayer`] does for every request; call it directly to
+    /// record metadata for writes made outside of a tower service.
+    pub fn scope<F: std::future::Future>(self, fut: F) -> TaskLocalFuture<ClientMetadata, F> {
+        CURRENT.scope(self, fut)
+    }
+}
+
+/// A function returning the IP address of the client from the headers and extensions of a
+/// request.
+pub type IpExtractor = Arc<dyn Fn(&HeaderMap, &http::Extensions) -> Option<IpAddr> + Send + Sync>;
+
+/// A tower layer recording the IP address and user agent of each request for the sessions
+/// written while handling it.
+///
+/// Add it outside of `SessionManagerLayer`, so the session is saved within its scope. By
+/// default, the IP address is read from a [`SocketAddr`] request extension; servers that
+/// store the peer address differently, such as axum's `ConnectInfo`, or applications behind a
+/// reverse proxy need [`ClientMetadataLayer::with_ip_extractor`] or
+/// [`ClientMetadataLayer::with_forwarded_for`].
+///
+/// **Note**: Only available when the `client-metadata` feature is enabled.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tower_sessions::SessionManagerLayer;
+/// use tower_sessions_seaorm_store::{ClientMetadataLayer, PostgresStore};
+///
+/// # fn example(store: PostgresStore) {
+/// // Layers added later wrap the earlier ones
+/// let app: axum::Router = axum::Router::new()
+///     .layer(SessionManagerLayer::new(store))
+///     .layer(ClientMetadataLayer::new().with_forwarded_for());
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ClientMetadataLayer {
+    ip_extractor: IpExtractor,
+}
+
+impl ClientMetadataLayer {
+    /// Creates a layer reading the IP address from a [`SocketAddr`] request extension.
+    pub fn new() -> Self {
+        Self {
+            ip_extractor: Arc::new(|_, extensions| extensions.get::<SocketAddr>().map(SocketAddr::ip)),
+        }
+    }
+
+    /// Reads the IP address of the client with `extractor`.
+    ///
+    /// # Parameters
+    ///
+    /// * `extractor` - Returns the IP address of the client from the headers and extensions
+    ///   of a request.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::net::SocketAddr;
+    ///
+    /// use axum::extract::ConnectInfo;
+    /// use tower_sessions_seaorm_store::ClientMetadataLayer;
+    ///
+    /// let layer = ClientMetadataLayer::new().with_ip_extractor(|_, extensions| {
+    ///     extensions
+    ///         .get::<ConnectInfo<SocketAddr>>()
+    ///         .map(|ConnectInfo(addr)| addr.ip())
+    /// });
+    /// ```
+    #[must_use = "this method returns a new value; the original is unchanged"]
+    pub fn with_ip_extractor(
+        mut self,
+        extractor: impl Fn(&HeaderMap, &http::Extensions) -> Option<IpAddr> + Send + Sync + 'static,
+    ) -> Self {
+        self.ip_extractor = Arc::new(extractor);
+        self
+    }
+
+    /// Reads the IP address of the client from the first entry of the `X-Forwarded-For`
+    /// header, falling back to the current extractor without one.
+    ///
+    /// Only use this behind a reverse proxy that sets the header, as clients can send any
+    /// value themselves.
+    #[must_use = "this method returns a new value; the original is unchanged"]
+    pub fn with_forwarded_for(self) -> Self {
+        let fallback = self.ip_extractor.clone();
+        self.with_ip_extractor(move |headers, extensions| {
+            forwarded_for(headers).or_else(|| fallback(headers, extensions))
+        })
+    }
+
+    /// Returns the client of `request`.
+    fn metadata<B>(&self, request: &Request<B>) -> ClientMetadata {
+        let user_agent = request
+            .headers()
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(|user_agent| truncate(user_agent, MAX_USER_AGENT_LEN).to_owned());
+
+        ClientMetadata {
+            ip_address: (self.ip_extractor)(request.headers(), request.extensions()),
+            user_agent,
+        }
+    }
+}
+
+impl Default for ClientMetadataLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ClientMetadataLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientMetadataLayer").finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for ClientMetadataLayer {
+    type Service = ClientMetadataService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ClientMetadataService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// The service created by [`ClientMetadataLayer`].
+#[derive(Clone, Debug)]
+pub struct ClientMetadataService<S> {
+    inner: S,
+    layer: ClientMetadataLayer,
+}
+
+impl<S, B> Service<Request<B>> for ClientMetadataService<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = TaskLocalFuture<ClientMetadata, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let metadata = self.layer.metadata(&request);
+        metadata.scope(self.inner.call(request))
+    }
+}
+
+/// Returns the first address of the `X-Forwarded-For` header, if it is valid.
+fn forwarded_for(headers: &HeaderMap) -> Option<IpAddr> {
+    headers
+        .get("x-forwarded-for")?
+        .to_str()
+        .ok()?
+        .split(',')
+        .next()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Returns `value` cut to at most `max_len` bytes at a character boundary.
+fn truncate(value: &str, max_len: usize) -> &str {
+    if value.len() <= max_len {
+        return value;
+    }
+
+    let mut end = max_len;
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    &value[..end]
+}
@@ -0,0 +1,27 @@
+//! zstd compression of MessagePack session payloads.
+//!
+//! Compressed payloads start with the `0xC1` tag of the payload envelope, followed by a zstd
+//! frame. Payloads written without compression carry another tag, or none, so they keep
+//! decoding as they are.
+
+use crate::envelope::COMPRESSED_TAG;
+
+/// Returns `encoded` compressed and tagged, or `None` when compressing doesn't make it smaller.
+pub(crate) fn compress(encoded: &[u8]) -> Result<Option<Vec<u8>>, crate::SeaOrmStoreError> {
+    let compressed = zstd::bulk::compress(encoded, zstd::DEFAULT_COMPRESSION_LEVEL)
+        .map_err(|err| crate::SeaOrmStoreError::Encode(err.to_string()))?;
+
+    if compressed.len() + 1 >= encoded.len() {
+        return Ok(None);
+    }
+
+    let mut payload = Vec::with_capacity(compressed.len() + 1);
+    payload.push(COMPRESSED_TAG);
+    payload.extend_from_slice(&compressed);
+    Ok(Some(payload))
+}
+
+/// Returns the MessagePack bytes of the zstd frame `compressed`, without its tag.
+pub(crate) fn decompress(compressed: &[u8]) -> Result<Vec<u8>, crate::SeaOrmStoreError> {
+    zstd::stream::decode_all(compressed).map_err(|err| crate::SeaOrmStoreError::Decode(err.to_string()))
+}
@@ -0,0 +1,26 @@
+//! Conditions for `PostgresStore::conditional_save`.
+
+use time::OffsetDateTime;
+
+/// The condition under which `PostgresStore::conditional_save` writes a session.
+///
+/// The condition is evaluated by the database as part of the write statement, so checking it
+/// and writing the session is a single atomic operation. Rows of expired sessions that have
+/// not been deleted yet still count as existing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConditionalSaveExpr {
+    /// Update the session only if its stored expiry date is earlier than the given date.
+    ///
+    /// Passing the new expiry date implements a sliding window that never shortens a session
+    /// which was already extended further by a concurrent request.
+    OnlyIfExpiryLessThan(OffsetDateTime),
+    /// Update the session only if it already exists.
+    OnlyIfExists,
+    /// Insert the session only if it doesn't exist yet.
+    OnlyIfNotExists,
+    /// Update the session only if its stored version equals the given version.
+    ///
+    /// Use `PostgresStore::session_version` to read the version before modifying a session, or
+    /// `PostgresStore::save_versioned` to get a `Conflict` error instead of `false`.
+    OnlyIfVersionEquals(u64),
+}
@@ -0,0 +1,151 @@
+//! Runner for the user-defined migrations registered with `PostgresStore::with_custom_migration`.
+//!
+//! The built-in `Migrator` has a fixed list of migrations, so custom migrations are tracked
+//! in their own table, `tower_sessions_seaorm_custom_migrations` (suffixed with the schema
+//! name outside the default schema). Each of them runs in a transaction together with the
+//! insertion of its tracking row, and is rolled back together with its deletion.
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbErr, TransactionTrait};
+use sea_orm_migration::prelude::*;
+
+/// The user-defined migrations of a store, in registration order.
+#[derive(Clone, Default)]
+pub(crate) struct CustomMigrations(Vec<Arc<dyn MigrationTrait>>);
+
+impl CustomMigrations {
+    pub(crate) fn push(&mut self, migration: Box<dyn MigrationTrait>) {
+        self.0.push(Arc::from(migration));
+    }
+
+    /// Returns the number of migrations that haven't been applied yet.
+    pub(crate) async fn pending(&self, conn: &DatabaseConnection) -> Result<usize, DbErr> {
+        if self.0.is_empty() {
+            return Ok(0);
+        }
+
+        let applied = applied_versions(conn).await?;
+        Ok(self
+            .0
+            .iter()
+            .filter(|migration| !applied.iter().any(|version| version == migration.name()))
+            .count())
+    }
+
+    /// Applies the migrations that haven't been applied yet, in registration order.
+    pub(crate) async fn up(&self, conn: &DatabaseConnection) -> Result<(), DbErr> {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+
+        SchemaManager::new(conn)
+            .create_table(
+                Table::create()
+                    .table(table())
+                    .if_not_exists()
+                    .col(ColumnDef::new(CustomMigration::Version).string().not_null().primary_key())
+                    .col(ColumnDef::new(CustomMigration::AppliedAt).big_integer().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        let applied = applied_versions(conn).await?;
+        for migration in &self.0 {
+            if applied.iter().any(|version| version == migration.name()) {
+                continue;
+            }
+
+            let txn = conn.begin().await?;
+            migration.up(&SchemaManager::new(&txn)).await?;
+
+            let applied_at = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs() as i64);
+            let insert = Query::insert()
+                .into_table(table())
+                .columns([CustomMigration::Version, CustomMigration::AppliedAt])
+                .values_panic([migration.name().into(), applied_at.into()])
+                .to_owned();
+            txn.execute(txn.get_database_backend().build(&insert)).await?;
+
+            txn.commit().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Rolls back up to `steps` applied migrations, or all of them if `steps` is `None`, in
+    /// reverse registration order.
+    ///
+    /// Returns the number of migrations rolled back.
+    pub(crate) async fn down(&self, conn: &DatabaseConnection, steps: Option<u32>) -> Result<u32, DbErr> {
+        if self.0.is_empty() {
+            return Ok(0);
+        }
+
+        let applied = applied_versions(conn).await?;
+        let mut rolled_back = 0;
+        for migration in self.0.iter().rev() {
+            if steps.is_some_and(|steps| rolled_back >= steps) {
+                break;
+            }
+            if !applied.iter().any(|version| version == migration.name()) {
+                continue;
+            }
+
+            let txn = conn.begin().await?;
+            migration.down(&SchemaManager::new(&txn)).await?;
+
+            let delete = Query::delete()
+                .from_table(table())
+                .and_where(Expr::col(CustomMigration::Version).eq(migration.name()))
+                .to_owned();
+            txn.execute(txn.get_database_backend().build(&delete)).await?;
+
+            txn.commit().await?;
+            rolled_back += 1;
+        }
+
+        Ok(rolled_back)
+    }
+}
+
+impl fmt::Debug for CustomMigrations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|migration| migration.name()))
+            .finish()
+    }
+}
+
+/// Returns the versions of the custom migrations already applied.
+async fn applied_versions(conn: &DatabaseConnection) -> Result<Vec<String>, DbErr> {
+    if !SchemaManager::new(conn).has_table(table().to_string()).await? {
+        return Ok(Vec::new());
+    }
+
+    let select = Query::select()
+        .column(CustomMigration::Version)
+        .from(table())
+        .to_owned();
+
+    conn.query_all(conn.get_database_backend().build(&select))
+        .await?
+        .iter()
+        .map(|row| row.try_get::<String>("", "version"))
+        .collect()
+}
+
+/// Returns the table tracking the custom migrations applied to the current schema.
+fn table() -> Alias {
+    crate::migration::tracking_table("tower_sessions_seaorm_custom_migrations")
+}
+
+#[derive(DeriveIden)]
+enum CustomMigration {
+    Version,
+    AppliedAt,
+}
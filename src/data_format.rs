@@ -0,0 +1,43 @@
+//! Encodings of session records in the `data` column.
+
+use tower_sessions::session::Record;
+
+use crate::envelope::PayloadVersion;
+
+/// How session records are encoded in the `data` column.
+///
+/// Configure the format with `PostgresStore::with_data_format`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SessionDataFormat {
+    /// Compact MessagePack in a `BYTEA` column (the default).
+    ///
+    /// The data starts with a tag naming its codec, see [`PayloadVersion`]. With the
+    /// `compression` feature, the MessagePack data is compressed with zstd whenever that makes
+    /// it smaller. Uncompressed data is still read, so existing sessions keep loading.
+    #[default]
+    MessagePack,
+    /// JSON in a `JSONB` column, so session contents can be inspected and queried with SQL.
+    ///
+    /// Only supported on PostgreSQL. The `data` column must have been converted to `JSONB`, which
+    /// `migrate()` does once this format is configured.
+    Jsonb,
+}
+
+impl SessionDataFormat {
+    /// Encodes `record` in this format, in the envelope of `version` for MessagePack.
+    pub(crate) fn encode(self, record: &Record, version: PayloadVersion) -> Result<Vec<u8>, crate::SeaOrmStoreError> {
+        match self {
+            SessionDataFormat::MessagePack => crate::envelope::encode(record, version),
+            // JSON can't carry a tag in a `JSONB` column
+            SessionDataFormat::Jsonb => Ok(serde_json::to_vec(record)?),
+        }
+    }
+
+    /// Decodes a record encoded in this format.
+    pub(crate) fn decode(self, data: &[u8]) -> Result<Record, crate::SeaOrmStoreError> {
+        match self {
+            SessionDataFormat::MessagePack => crate::envelope::decode(data),
+            SessionDataFormat::Jsonb => Ok(serde_json::from_slice(data)?),
+        }
+    }
+}
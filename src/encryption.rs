@@ -0,0 +1,287 @@
+//! AES-256-GCM encryption of session payloads.
+//!
+//! Encrypted payloads start with `0xC2`, which MessagePack uses for `false` and therefore never
+//! starts an encoded session record, followed by the ID of the key, a random 96-bit nonce and
+//! the ciphertext with its authentication tag. The ID of the row is authenticated along with the
+//! ciphertext, so a payload copied into another row doesn't decrypt.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+
+/// First byte of an encrypted payload, followed by the key ID.
+const ENCRYPTED_MARKER: u8 = 0xC2;
+
+/// Length of the nonce following the marker and key ID.
+const NONCE_LEN: usize = 12;
+
+/// Supplies the keys session data is encrypted with.
+///
+/// Keys are requested for every encryption and decryption, so implementations backed by a
+/// secrets manager should cache them rather than fetch them each time. [`StaticKeyProvider`]
+/// covers a single key held in memory, in an environment variable or in a file, and [`KeyRing`]
+/// several keys during a key rotation.
+///
+/// Every key has a one-byte ID, stored with the data it encrypted. New data is encrypted with
+/// [`KeyProvider::key`], whose ID is [`KeyProvider::key_id`], and stored data is decrypted with
+/// [`KeyProvider::key_by_id`]. Providers of a single key only need to implement `key`.
+///
+/// # Examples
+///
+/// ```
+/// use tower_sessions_seaorm_store::KeyProvider;
+///
+/// struct VaultKey {
+///     cached: [u8; 32],
+/// }
+///
+/// impl KeyProvider for VaultKey {
+///     fn key(&self) -> Result<[u8; 32], String> {
+///         Ok(self.cached)
+///     }
+/// }
+/// ```
+pub trait KeyProvider: Send + Sync + 'static {
+    /// Returns the 256-bit AES key new data is encrypted with, or a message describing why it
+    /// isn't available.
+    fn key(&self) -> Result<[u8; 32], String>;
+
+    /// Returns the ID of the key returned by [`KeyProvider::key`].
+    fn key_id(&self) -> u8 {
+        0
+    }
+
+    /// Returns the key with the ID `key_id`, or a message describing why it isn't available.
+    ///
+    /// By default, only the ID of the current key is known.
+    fn key_by_id(&self, key_id: u8) -> Result<[u8; 32], String> {
+        if key_id == self.key_id() {
+            self.key()
+        } else {
+            Err(format!("unknown key id {key_id}"))
+        }
+    }
+}
+
+/// A [`KeyProvider`] returning a key loaded once.
+#[derive(Clone)]
+pub struct StaticKeyProvider([u8; 32]);
+
+impl StaticKeyProvider {
+    /// Creates a provider for `key`.
+    pub fn new(key: [u8; 32]) -> Self {
+        Self(key)
+    }
+
+    /// Creates a provider for the key encoded as 64 hexadecimal digits in `hex`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SeaOrmStoreError::EncryptionKey`](crate::SeaOrmStoreError::EncryptionKey) if
+    /// `hex` isn't a hex-encoded 256-bit key.
+    pub fn from_hex(hex: &str) -> Result<Self, crate::SeaOrmStoreError> {
+        let hex = hex.trim();
+        let invalid = || crate::SeaOrmStoreError::EncryptionKey("expected 64 hexadecimal digits".to_string());
+        if hex.len() != 64 || !hex.is_ascii() {
+            return Err(invalid());
+        }
+
+        let mut key = [0; 32];
+        for (byte, digits) in key.iter_mut().zip(hex.as_bytes().chunks(2)) {
+            let digits = std::str::from_utf8(digits).map_err(|_| invalid())?;
+            *byte = u8::from_str_radix(digits, 16).map_err(|_| invalid())?;
+        }
+        Ok(Self(key))
+    }
+
+    /// Creates a provider for the hex-encoded key in the environment variable `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SeaOrmStoreError::EncryptionKey`](crate::SeaOrmStoreError::EncryptionKey) if
+    /// the variable isn't set or doesn't hold a hex-encoded 256-bit key.
+    pub fn from_env(name: &str) -> Result<Self, crate::SeaOrmStoreError> {
+        let hex = std::env::var(name)
+            .map_err(|err| crate::SeaOrmStoreError::EncryptionKey(format!("{name}: {err}")))?;
+        Self::from_hex(&hex)
+    }
+
+    /// Creates a provider for the hex-encoded key in the file at `path`.
+    ///
+    /// Surrounding whitespace, such as a trailing newline, is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SeaOrmStoreError::EncryptionKey`](crate::SeaOrmStoreError::EncryptionKey) if
+    /// the file can't be read or doesn't hold a hex-encoded 256-bit key.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, crate::SeaOrmStoreError> {
+        let path = path.as_ref();
+        let hex = std::fs::read_to_string(path)
+            .map_err(|err| crate::SeaOrmStoreError::EncryptionKey(format!("{}: {err}", path.display())))?;
+        Self::from_hex(&hex)
+    }
+}
+
+impl KeyProvider for StaticKeyProvider {
+    fn key(&self) -> Result<[u8; 32], String> {
+        Ok(self.0)
+    }
+}
+
+// The key must not end up in logs
+impl fmt::Debug for StaticKeyProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("StaticKeyProvider").finish_non_exhaustive()
+    }
+}
+
+/// A [`KeyProvider`] holding several keys, for rotating the encryption key.
+///
+/// The key added last is the current one, which new data is encrypted with. The other keys
+/// are only used to decrypt data they encrypted, until `PostgresStore::rewrap_all` has
+/// re-encrypted it with the current key and they can be removed.
+///
+/// Data encrypted by a [`StaticKeyProvider`] carries key ID 0, so add the previous key with
+/// that ID when moving from a single key to a key ring.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tower_sessions_seaorm_store::{KeyRing, StaticKeyProvider};
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let keys = KeyRing::new()
+///     .with_key(0, StaticKeyProvider::from_env("SESSION_KEY_OLD")?)
+///     .with_key(1, StaticKeyProvider::from_env("SESSION_KEY")?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct KeyRing {
+    keys: BTreeMap<u8, [u8; 32]>,
+    current: Option<u8>,
+}
+
+impl KeyRing {
+    /// Creates an empty key ring.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `key` with the ID `key_id` and makes it the current key.
+    ///
+    /// A key previously added with the same ID is replaced.
+    ///
+    /// # Parameters
+    ///
+    /// * `key_id` - The ID stored with the data `key` encrypts.
+    /// * `key` - The key.
+    #[must_use = "this method returns a new value; the original is unchanged"]
+    pub fn with_key(mut self, key_id: u8, key: StaticKeyProvider) -> Self {
+        self.keys.insert(key_id, key.0);
+        self.current = Some(key_id);
+        self
+    }
+}
+
+impl KeyProvider for KeyRing {
+    fn key(&self) -> Result<[u8; 32], String> {
+        self.current
+            .map(|key_id| self.keys[&key_id])
+            .ok_or_else(|| "the key ring is empty".to_string())
+    }
+
+    fn key_id(&self) -> u8 {
+        self.current.unwrap_or_default()
+    }
+
+    fn key_by_id(&self, key_id: u8) -> Result<[u8; 32], String> {
+        self.keys
+            .get(&key_id)
+            .copied()
+            .ok_or_else(|| format!("unknown key id {key_id}"))
+    }
+}
+
+// Only the key IDs may end up in logs
+impl fmt::Debug for KeyRing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyRing")
+            .field("key_ids", &self.keys.keys().collect::<Vec<_>>())
+            .field("current", &self.current)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The key provider of a store, cloned along with the store.
+#[derive(Clone)]
+pub(crate) struct Encryption(Arc<dyn KeyProvider>);
+
+impl Encryption {
+    pub(crate) fn new(provider: Arc<dyn KeyProvider>) -> Self {
+        Self(provider)
+    }
+
+    fn cipher(key: Result<[u8; 32], String>) -> Result<Aes256Gcm, crate::SeaOrmStoreError> {
+        let key = key.map_err(crate::SeaOrmStoreError::EncryptionKey)?;
+        Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)))
+    }
+
+    /// Returns `payload` encrypted for the row `row_id` with the current key and a fresh nonce,
+    /// and marked.
+    pub(crate) fn encrypt(&self, row_id: &str, payload: &[u8]) -> Result<Vec<u8>, crate::SeaOrmStoreError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let payload = Payload {
+            msg: payload,
+            aad: row_id.as_bytes(),
+        };
+        let ciphertext = Self::cipher(self.0.key())?
+            .encrypt(&nonce, payload)
+            .map_err(|_| crate::SeaOrmStoreError::Encode("failed to encrypt session data".to_string()))?;
+
+        let mut encrypted = Vec::with_capacity(2 + NONCE_LEN + ciphertext.len());
+        encrypted.push(ENCRYPTED_MARKER);
+        encrypted.push(self.0.key_id());
+        encrypted.extend_from_slice(&nonce);
+        encrypted.extend_from_slice(&ciphertext);
+        Ok(encrypted)
+    }
+
+    /// Returns the plaintext of `payload` stored in the row `row_id`, or `None` if it isn't
+    /// marked as encrypted.
+    pub(crate) fn decrypt(&self, row_id: &str, payload: &[u8]) -> Result<Option<Vec<u8>>, crate::SeaOrmStoreError> {
+        let (key_id, encrypted) = match payload {
+            [ENCRYPTED_MARKER, key_id, encrypted @ ..] => (*key_id, encrypted),
+            _ => return Ok(None),
+        };
+        if encrypted.len() < NONCE_LEN {
+            return Err(crate::SeaOrmStoreError::Decode("truncated encrypted session data".to_string()));
+        }
+
+        let (nonce, ciphertext) = encrypted.split_at(NONCE_LEN);
+        let payload = Payload {
+            msg: ciphertext,
+            aad: row_id.as_bytes(),
+        };
+        Self::cipher(self.0.key_by_id(key_id))?
+            .decrypt(Nonce::from_slice(nonce), payload)
+            .map(Some)
+            // Wrong key, wrong row or tampered data, the tag doesn't tell which
+            .map_err(|_| crate::SeaOrmStoreError::Decode("failed to decrypt session data".to_string()))
+    }
+
+    /// Returns whether `payload` is encrypted with the current key.
+    pub(crate) fn is_current(&self, payload: &[u8]) -> bool {
+        matches!(payload, [ENCRYPTED_MARKER, key_id, ..] if *key_id == self.0.key_id())
+    }
+}
+
+impl fmt::Debug for Encryption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Encryption").finish_non_exhaustive()
+    }
+}
@@ -16,11 +16,17 @@ use sea_orm::entity::prelude::*;
 ///
 /// The database schema for this model contains the following columns:
 ///
-/// | Column      | Type                    | Description                       |
-/// |-------------|-------------------------|-----------------------------------|
-/// | id          | TEXT (Primary Key)      | Session ID                        |
-/// | data        | BYTEA                   | Serialized session data           |
-/// | expiry_date | TIMESTAMPTZ             | Session expiration timestamp      |
+/// | Column           | Type               | Description                       |
+/// |------------------|--------------------|-----------------------------------|
+/// | id               | TEXT (Primary Key) | Session ID                        |
+/// | data             | BYTEA              | Serialized session data           |
+/// | expiry_date      | TIMESTAMPTZ        | Session expiration timestamp      |
+/// | last_accessed_at | TIMESTAMPTZ        | Time of the last write            |
+/// | version          | BIGINT             | Number of updates to the session  |
+/// | user_id          | TEXT (Nullable)    | Owner of the session, if known    |
+/// | created_at       | TIMESTAMPTZ (Null) | Time the session was created      |
+/// | updated_at       | TIMESTAMPTZ (Null) | Time the session was last saved   |
+/// | data_hash        | BIGINT (Nullable)  | Hash of the session data          |
 ///
 /// # Usage
 ///
@@ -53,6 +59,63 @@ pub struct Model {
     ///
     /// It's stored as a `TIMESTAMPTZ` in PostgreSQL.
     pub expiry_date: DateTimeWithTimeZone,
+
+    /// The timestamp of the last time the session was written.
+    ///
+    /// This field is refreshed by every `create()` and `save()` call and is used
+    /// by `PostgresStore::with_idle_session_ttl` to expire sessions that have not
+    /// been touched for longer than the configured idle TTL.
+    ///
+    /// It's stored as a `TIMESTAMPTZ` in PostgreSQL.
+    pub last_accessed_at: DateTimeWithTimeZone,
+
+    /// The number of times the session has been updated since it was created.
+    ///
+    /// New sessions start at version 0 and every update increments the version.
+    /// `PostgresStore::conditional_save` uses it for optimistic locking.
+    ///
+    /// It's stored as a `BIGINT` in PostgreSQL.
+    pub version: i64,
+
+    /// The ID of the user owning the session, if known.
+    ///
+    /// This field is extracted from the session data on every `create()` and `save()` call
+    /// by the extractor configured with `PostgresStore::with_user_id_extractor`, and is
+    /// `NULL` without one. It's indexed, so the sessions of a user can be found without
+    /// decoding every session.
+    ///
+    /// It's stored as a nullable `TEXT` in PostgreSQL.
+    #[sea_orm(column_type = "Text", nullable)]
+    pub user_id: Option<String>,
+
+    /// The timestamp of the creation of the session.
+    ///
+    /// This field is set when the row is inserted and never changed afterwards, so the age
+    /// of a session can be read without decoding its data. It's `NULL` for sessions created
+    /// before the column was added.
+    ///
+    /// It's stored as a nullable `TIMESTAMPTZ` in PostgreSQL.
+    #[sea_orm(nullable)]
+    pub created_at: Option<DateTimeWithTimeZone>,
+
+    /// The timestamp of the last time the session was saved.
+    ///
+    /// This field is set on every write of the session data. It's `NULL` for sessions not
+    /// written since the column was added.
+    ///
+    /// It's stored as a nullable `TIMESTAMPTZ` in PostgreSQL.
+    #[sea_orm(nullable)]
+    pub updated_at: Option<DateTimeWithTimeZone>,
+
+    /// A hash of the session data, independent of its encoding.
+    ///
+    /// This field is only set by stores configured with
+    /// `PostgresStore::with_skip_unchanged_saves`, which compare it to skip saves that don't
+    /// change the data. It's `NULL` for sessions written without one.
+    ///
+    /// It's stored as a nullable `BIGINT` in PostgreSQL.
+    #[sea_orm(nullable)]
+    pub data_hash: Option<i64>,
 }
 
 /// Required enum for Sea-ORM entity relations.
@@ -0,0 +1,163 @@
+//! Versioned envelope of MessagePack session payloads.
+//!
+//! The first byte of a payload is a tag naming how the rest of it is encoded, so `load()`
+//! picks the decoder per row, and rows written by older or newer versions of the application
+//! coexist in one table. MessagePack encodes records as arrays, so the tags are bytes that never
+//! start one:
+//!
+//! | Tag    | Payload                                                      |
+//! |--------|--------------------------------------------------------------|
+//! | `0xC1` | zstd-compressed MessagePack                                  |
+//! | `0xC2` | Encrypted, followed by the key ID                            |
+//! | `0xC3` | MessagePack                                                  |
+//! | `{`    | JSON, as written by `reencode_all` ahead of a `JSONB` column |
+//!
+//! Encrypted payloads hold a tagged payload once decrypted. Payloads starting with a MessagePack
+//! array or map were written before the envelope, and are decoded as plain MessagePack.
+
+use tower_sessions::session::Record;
+
+/// Tag of an uncompressed MessagePack payload.
+const MESSAGE_PACK_TAG: u8 = 0xC3;
+
+/// Tag of a zstd-compressed MessagePack payload.
+pub(crate) const COMPRESSED_TAG: u8 = 0xC1;
+
+/// Tag of an encrypted payload, which is decrypted before it is decoded.
+const ENCRYPTED_TAG: u8 = 0xC2;
+
+/// First byte of a JSON payload, the opening brace of the record object.
+const JSON_TAG: u8 = b'{';
+
+/// The envelope MessagePack session payloads are written in.
+///
+/// Every version keeps reading the payloads of the versions before it, so the version only
+/// matters to older deployments of the application sharing the table. Configure it with
+/// `PostgresStore::with_payload_version`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PayloadVersion {
+    /// Plain MessagePack without a tag, as written before the envelope existed.
+    ///
+    /// Write this version while deployments from before the envelope still read the table,
+    /// then switch to [`PayloadVersion::V1`] once they are gone. Compressed payloads are
+    /// tagged either way.
+    Untagged,
+    /// MessagePack behind a one-byte tag naming its codec (the default).
+    #[default]
+    V1,
+}
+
+/// A codec of session payloads in a `BYTEA` column, as converted by
+/// `PostgresStore::reencode_all`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PayloadCodec {
+    /// Uncompressed MessagePack, tagged or written before the envelope.
+    MessagePack,
+    /// zstd-compressed MessagePack.
+    ///
+    /// Payloads that compression doesn't make smaller are written as
+    /// [`PayloadCodec::MessagePack`], as the store does when writing sessions.
+    ///
+    /// **Note**: Only available when the `compression` feature is enabled.
+    #[cfg(feature = "compression")]
+    Zstd,
+    /// JSON text, which `migrate()` converts in SQL when switching to `SessionDataFormat::Jsonb`.
+    Json,
+}
+
+/// How far `PostgresStore::reencode_all` got, reported after every batch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ReencodeProgress {
+    /// Number of sessions read so far.
+    pub scanned: u64,
+    /// Number of sessions converted to the target codec so far.
+    pub reencoded: u64,
+    /// Number of sessions in the source codec that couldn't be decoded, and were left as is.
+    pub failed: u64,
+}
+
+/// Encodes `record` as MessagePack in the envelope of `version`, compressed with the
+/// `compression` feature whenever that makes it smaller.
+pub(crate) fn encode(record: &Record, version: PayloadVersion) -> Result<Vec<u8>, crate::SeaOrmStoreError> {
+    #[cfg(feature = "compression")]
+    let codec = PayloadCodec::Zstd;
+    #[cfg(not(feature = "compression"))]
+    let codec = PayloadCodec::MessagePack;
+
+    encode_as(record, codec, version)
+}
+
+/// Encodes `record` with `codec`, in the envelope of `version` for MessagePack.
+pub(crate) fn encode_as(
+    record: &Record,
+    codec: PayloadCodec,
+    version: PayloadVersion,
+) -> Result<Vec<u8>, crate::SeaOrmStoreError> {
+    let encoded = match codec {
+        PayloadCodec::Json => return Ok(serde_json::to_vec(record)?),
+        PayloadCodec::MessagePack => rmp_serde::to_vec(record)?,
+        #[cfg(feature = "compression")]
+        PayloadCodec::Zstd => {
+            let encoded = rmp_serde::to_vec(record)?;
+            match crate::compression::compress(&encoded)? {
+                Some(compressed) => return Ok(compressed),
+                None => encoded,
+            }
+        }
+    };
+
+    Ok(match version {
+        PayloadVersion::Untagged => encoded,
+        PayloadVersion::V1 => [&[MESSAGE_PACK_TAG][..], &encoded].concat(),
+    })
+}
+
+/// Returns the codec of the unencrypted payload `payload`, or `None` if its tag is unknown.
+pub(crate) fn codec(payload: &[u8]) -> Option<PayloadCodec> {
+    match payload.first()? {
+        &MESSAGE_PACK_TAG | 0x80..=0x9F | 0xDC..=0xDF => Some(PayloadCodec::MessagePack),
+        #[cfg(feature = "compression")]
+        &COMPRESSED_TAG => Some(PayloadCodec::Zstd),
+        &JSON_TAG => Some(PayloadCodec::Json),
+        _ => None,
+    }
+}
+
+/// Returns whether the unencrypted payload `payload` is written in the envelope of `version`.
+///
+/// Only MessagePack payloads can be written without a tag, others always match.
+pub(crate) fn has_version(payload: &[u8], version: PayloadVersion) -> bool {
+    match codec(payload) {
+        Some(PayloadCodec::MessagePack) => (payload[0] == MESSAGE_PACK_TAG) == (version == PayloadVersion::V1),
+        _ => true,
+    }
+}
+
+/// Decodes a MessagePack payload with the codec named by its tag.
+///
+/// # Errors
+///
+/// Returns [`SeaOrmStoreError::Decode`](crate::SeaOrmStoreError::Decode) if the payload is
+/// empty, encrypted, compressed without the `compression` feature, or has a tag this version
+/// doesn't know, such as one written by a newer version of the application.
+pub(crate) fn decode(payload: &[u8]) -> Result<Record, crate::SeaOrmStoreError> {
+    match payload.split_first() {
+        Some((&MESSAGE_PACK_TAG, encoded)) => Ok(rmp_serde::from_slice(encoded)?),
+        #[cfg(feature = "compression")]
+        Some((&COMPRESSED_TAG, compressed)) => Ok(rmp_serde::from_slice(&crate::compression::decompress(compressed)?)?),
+        #[cfg(not(feature = "compression"))]
+        Some((&COMPRESSED_TAG, _)) => Err(crate::SeaOrmStoreError::Decode(
+            "session data is compressed, which requires the `compression` feature".to_string(),
+        )),
+        Some((&ENCRYPTED_TAG, _)) => Err(crate::SeaOrmStoreError::Decode(
+            "session data is encrypted, but no key provider is configured".to_string(),
+        )),
+        Some((&JSON_TAG, _)) => Ok(serde_json::from_slice(payload)?),
+        // Untagged payloads start with the array or map of the record
+        Some((0x80..=0x9F | 0xDC..=0xDF, _)) => Ok(rmp_serde::from_slice(payload)?),
+        Some((tag, _)) => Err(crate::SeaOrmStoreError::Decode(format!(
+            "unsupported session payload tag 0x{tag:02X}"
+        ))),
+        None => Err(crate::SeaOrmStoreError::Decode("empty session data".to_string())),
+    }
+}
@@ -0,0 +1,16 @@
+//! Policies for the per-user session limit of `PostgresStore::with_max_sessions_per_user`.
+
+/// Which sessions of a user are evicted once the user holds more than the configured maximum.
+///
+/// Used with `PostgresStore::with_max_sessions_per_user`. The sessions kept are always the
+/// newest by the order of the policy, so concurrent writes agree on which sessions to evict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EvictionPolicy {
+    /// Evict the sessions created first, so logging in on a new device logs out the device
+    /// that logged in longest ago.
+    ///
+    /// Sessions created before the `created_at` column was filled count as the oldest.
+    OldestFirst,
+    /// Evict the sessions written least recently, so the devices still in use stay logged in.
+    LeastRecentlyUsed,
+}
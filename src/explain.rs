@@ -0,0 +1,58 @@
+//! Typed representation of PostgreSQL query plans.
+//!
+//! `PostgresStore::explain_plan` runs `EXPLAIN (FORMAT JSON)` on the query behind a store
+//! operation and parses the output into a [`QueryPlan`] tree. This makes it possible to assert
+//! in tests that session lookups use an index instead of a sequential scan.
+
+use serde::Deserialize;
+
+/// A node of a PostgreSQL query plan, as reported by `EXPLAIN (FORMAT JSON)`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct QueryPlan {
+    /// The type of the plan node, for example `"Index Scan"` or `"Seq Scan"`.
+    #[serde(rename = "Node Type")]
+    pub node_type: String,
+
+    /// The estimated total cost of the node.
+    #[serde(rename = "Total Cost")]
+    pub total_cost: f64,
+
+    /// The estimated number of rows produced by the node.
+    #[serde(rename = "Plan Rows")]
+    pub rows: u64,
+
+    /// The estimated average width of the rows in bytes.
+    #[serde(rename = "Plan Width")]
+    pub width: u32,
+
+    /// The child nodes of this node.
+    #[serde(rename = "Plans", default)]
+    pub children: Vec<QueryPlan>,
+
+    /// The name of the index used by this node, if any.
+    #[serde(rename = "Index Name", default)]
+    pub index_used: Option<String>,
+}
+
+impl QueryPlan {
+    /// Returns `true` if this node or any of its children scans an index.
+    pub fn is_index_scan(&self) -> bool {
+        matches!(
+            self.node_type.as_str(),
+            "Index Scan" | "Index Only Scan" | "Bitmap Index Scan"
+        ) || self.children.iter().any(QueryPlan::is_index_scan)
+    }
+
+    /// Parses the output of `EXPLAIN (FORMAT JSON)`.
+    pub(crate) fn from_explain_output(output: serde_json::Value) -> Result<Self, serde_json::Error> {
+        #[derive(Deserialize)]
+        struct ExplainEntry {
+            #[serde(rename = "Plan")]
+            plan: QueryPlan,
+        }
+
+        // PostgreSQL returns a single-element array wrapping the root plan node
+        let [entry]: [ExplainEntry; 1] = serde_json::from_value(output)?;
+        Ok(entry.plan)
+    }
+}
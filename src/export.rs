@@ -0,0 +1,52 @@
+//! Newline-delimited JSON representation of sessions, written by `PostgresStore::export` and
+//! read by `PostgresStore::import`.
+//!
+//! Every line holds one session as a JSON object with its `id`, its `expiry_date` in RFC 3339
+//! format and its `data`, the MessagePack-encoded session keys in base64. The data is decoded
+//! from the stored format, so a dump can be imported into a store with a different data
+//! format or encryption key.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use tower_sessions::session::{Id, Record};
+
+/// A session as exported on one line.
+#[derive(Serialize, Deserialize)]
+struct ExportedSession {
+    id: String,
+    #[serde(with = "time::serde::rfc3339")]
+    expiry_date: OffsetDateTime,
+    data: String,
+}
+
+/// Returns `record` as a line of a dump, without the trailing newline.
+pub(crate) fn encode_line(record: &Record) -> Result<String, crate::SeaOrmStoreError> {
+    let exported = ExportedSession {
+        id: record.id.to_string(),
+        expiry_date: record.expiry_date,
+        data: STANDARD.encode(rmp_serde::to_vec(&record.data)?),
+    };
+    Ok(serde_json::to_string(&exported)?)
+}
+
+/// Returns the session on a line of a dump.
+pub(crate) fn decode_line(line: &str) -> Result<Record, crate::SeaOrmStoreError> {
+    let exported: ExportedSession = serde_json::from_str(line)?;
+    let id = Id::from_str(&exported.id)
+        .map_err(|err| crate::SeaOrmStoreError::Decode(format!("invalid session id: {err}")))?;
+    let data = STANDARD
+        .decode(&exported.data)
+        .map_err(|err| crate::SeaOrmStoreError::Decode(format!("invalid session data: {err}")))?;
+    let data: HashMap<String, serde_json::Value> = rmp_serde::from_slice(&data)?;
+
+    Ok(Record {
+        id,
+        data,
+        expiry_date: exported.expiry_date,
+    })
+}
@@ -0,0 +1,110 @@
+//! Failover to a secondary database while the primary is unreachable.
+//!
+//! When enabled via `PostgresStore::with_failover`, an operation failing on the primary
+//! database is followed by a ping of the primary. If the primary doesn't answer, it is
+//! considered unreachable; once it has been unreachable for the configured period, operations
+//! are routed to the secondary database. A background task then pings the primary every
+//! probe interval and routes operations back to it as soon as it answers.
+
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+use sea_orm::DatabaseConnection;
+use tower_sessions::session_store;
+
+/// Time between two pings of the primary while operations go to the secondary.
+const PROBE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Where operations are routed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Route {
+    /// Operations go to the primary, which has been unreachable since the given time, if set.
+    Primary { unreachable_since: Option<Instant> },
+    /// Operations go to the secondary until the primary answers a probe.
+    Secondary,
+}
+
+/// Shared failover state, cloned along with the store.
+#[derive(Debug, Clone)]
+pub(crate) struct Failover {
+    secondary: DatabaseConnection,
+    failover_after: Duration,
+    route: Arc<Mutex<Route>>,
+}
+
+impl Failover {
+    pub(crate) fn new(secondary: DatabaseConnection, failover_after: Duration) -> Self {
+        Self {
+            secondary,
+            failover_after,
+            route: Arc::new(Mutex::new(Route::Primary {
+                unreachable_since: None,
+            })),
+        }
+    }
+
+    /// Returns `true` if operations are routed to the secondary.
+    pub(crate) fn is_active(&self) -> bool {
+        *self.route.lock().expect("failover state poisoned") == Route::Secondary
+    }
+
+    /// Returns the secondary database connection.
+    pub(crate) fn secondary(&self) -> &DatabaseConnection {
+        &self.secondary
+    }
+
+    /// Records the outcome of an operation run on `primary`, and fails over if needed.
+    ///
+    /// Only backend errors after which `primary` doesn't answer a ping count as the primary
+    /// being unreachable; other errors, such as a missing table, leave the route unchanged.
+    pub(crate) async fn record<T>(&self, result: &session_store::Result<T>, primary: &DatabaseConnection) {
+        let unreachable = match result {
+            Err(session_store::Error::Backend(_)) => primary.ping().await.is_err(),
+            _ => false,
+        };
+        let now = Instant::now();
+        let mut route = self.route.lock().expect("failover state poisoned");
+
+        *route = match (*route, unreachable) {
+            (Route::Primary { .. }, false) => Route::Primary {
+                unreachable_since: None,
+            },
+            (Route::Primary { unreachable_since }, true) => {
+                let unreachable_since = unreachable_since.unwrap_or(now);
+                if now.duration_since(unreachable_since) >= self.failover_after {
+                    self.spawn_probe(primary.clone());
+                    Route::Secondary
+                } else {
+                    Route::Primary {
+                        unreachable_since: Some(unreachable_since),
+                    }
+                }
+            }
+            // Operations that started before the failover don't change anything
+            (Route::Secondary, _) => Route::Secondary,
+        };
+    }
+
+    /// Spawns a task that pings the primary until it answers, then routes operations back to it.
+    fn spawn_probe(&self, primary: DatabaseConnection) {
+        let route: Weak<Mutex<Route>> = Arc::downgrade(&self.route);
+
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(PROBE_INTERVAL).await;
+
+                // Stop probing once every store sharing this state has been dropped
+                let Some(route) = route.upgrade() else {
+                    return;
+                };
+
+                if primary.ping().await.is_ok() {
+                    *route.lock().expect("failover state poisoned") = Route::Primary {
+                        unreachable_since: None,
+                    };
+                    return;
+                }
+            }
+        });
+    }
+}
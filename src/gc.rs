@@ -0,0 +1,54 @@
+//! Description of the garbage collection configured for a store.
+
+use std::fmt;
+
+use serde::{Serialize, Serializer};
+use time::Duration;
+
+/// How expired sessions are cleaned up, as returned by `PostgresStore::gc_policy`.
+///
+/// Settings that aren't configured are `None`, and `false` for flags. The policy serializes to
+/// JSON with durations in whole seconds, which makes it easy to include in health check
+/// responses, and its `Display` implementation prints a one-line summary for logs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct GarbageCollectionPolicy {
+    /// Whether expired sessions are deleted when the store starts.
+    ///
+    /// Always `false` for now, as no startup check deletes expired sessions.
+    pub delete_expired_on_start: bool,
+    /// Time between two automatic deletions of expired sessions.
+    #[serde(serialize_with = "serialize_seconds")]
+    pub cleanup_interval: Option<Duration>,
+    /// Maximum number of sessions removed by a single delete statement of the cleanup task,
+    /// as set with `PostgresStore::with_cleanup_batch_size`.
+    pub batch_size: Option<usize>,
+    /// Inactivity period after which sessions are considered expired.
+    #[serde(serialize_with = "serialize_seconds")]
+    pub idle_session_ttl: Option<Duration>,
+    /// Time soft-deleted sessions are kept before being removed for good.
+    #[serde(serialize_with = "serialize_seconds")]
+    pub soft_delete_retention: Option<Duration>,
+}
+
+impl fmt::Display for GarbageCollectionPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn or_none(duration: Option<Duration>) -> String {
+            duration.map_or_else(|| "none".to_string(), |duration| duration.to_string())
+        }
+
+        write!(
+            f,
+            "delete expired on start: {}, cleanup interval: {}, batch size: {}, idle session TTL: {}, soft delete retention: {}",
+            if self.delete_expired_on_start { "yes" } else { "no" },
+            or_none(self.cleanup_interval),
+            self.batch_size.map_or_else(|| "unlimited".to_string(), |size| size.to_string()),
+            or_none(self.idle_session_ttl),
+            or_none(self.soft_delete_retention),
+        )
+    }
+}
+
+/// Serializes an optional duration as whole seconds.
+fn serialize_seconds<S: Serializer>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+    duration.map(|duration| duration.whole_seconds()).serialize(serializer)
+}
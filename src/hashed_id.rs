@@ -0,0 +1,9 @@
+//! Hashing of session IDs before they are written to the database.
+
+use sha2::{Digest, Sha256};
+use tower_sessions::session::Id;
+
+/// Returns the hex-encoded SHA-256 hash of the string form of `id`, the cookie value.
+pub(crate) fn hash_id(id: &Id) -> String {
+    format!("{:x}", Sha256::digest(id.to_string()))
+}
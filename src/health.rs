@@ -0,0 +1,60 @@
+//! Result of the readiness check of a store.
+
+use std::fmt;
+use std::time::Duration;
+
+use serde::{Serialize, Serializer};
+
+/// The state of the database of a store, as returned by `PostgresStore::health_check`.
+///
+/// The report serializes to JSON with the ping latency in whole milliseconds, so it can be
+/// returned by a readiness endpoint as is, and its `Display` implementation prints a one-line
+/// summary for logs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct HealthReport {
+    /// Whether the database answered the ping.
+    pub database_reachable: bool,
+    /// Time the database took to answer the ping.
+    #[serde(serialize_with = "serialize_millis")]
+    pub ping_latency: Option<Duration>,
+    /// Whether the session table exists.
+    pub table_exists: bool,
+    /// Columns used by the store that the session table lacks.
+    pub missing_columns: Vec<String>,
+    /// The error that made the check fail, if any.
+    pub error: Option<String>,
+}
+
+impl HealthReport {
+    /// Returns whether the store can serve requests: the database is reachable and the
+    /// session table has every column the store uses.
+    pub fn is_healthy(&self) -> bool {
+        self.database_reachable && self.table_exists && self.missing_columns.is_empty() && self.error.is_none()
+    }
+}
+
+impl fmt::Display for HealthReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, database reachable: {}, ping latency: {}, table exists: {}, missing columns: {}",
+            if self.is_healthy() { "healthy" } else { "unhealthy" },
+            if self.database_reachable { "yes" } else { "no" },
+            self.ping_latency
+                .map_or_else(|| "none".to_string(), |latency| format!("{}ms", latency.as_millis())),
+            if self.table_exists { "yes" } else { "no" },
+            if self.missing_columns.is_empty() { "none".to_string() } else { self.missing_columns.join(", ") },
+        )?;
+        if let Some(error) = &self.error {
+            write!(f, ", error: {error}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Serializes an optional duration as whole milliseconds.
+fn serialize_millis<S: Serializer>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+    duration
+        .map(|duration| u64::try_from(duration.as_millis()).unwrap_or(u64::MAX))
+        .serialize(serializer)
+}
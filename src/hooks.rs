@@ -0,0 +1,73 @@
+//! Asynchronous callbacks on the session lifecycle.
+//!
+//! Unlike a [`SessionActivityTracker`](crate::SessionActivityTracker), whose hooks are
+//! synchronous and must not block, [`SessionHooks`] are awaited by the store, so they can do
+//! I/O such as invalidating an application-level cache or publishing an event.
+
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tower_sessions::session::{Id, Record};
+
+/// Callbacks fired by the store after session lifecycle events.
+///
+/// Every hook has an empty default implementation, so implementors only override the events
+/// they need. Hooks are awaited once the operation has succeeded and before it returns to the
+/// caller, so their latency adds to that of the operation; they are never called for failed
+/// operations, and can't make an operation fail.
+///
+/// # Examples
+///
+/// ```
+/// use async_trait::async_trait;
+/// use tower_sessions::session::Id;
+/// use tower_sessions_seaorm_store::SessionHooks;
+///
+/// struct InvalidateCache;
+///
+/// #[async_trait]
+/// impl SessionHooks for InvalidateCache {
+///     async fn on_delete(&self, id: &Id) {
+///         // Remove the session from the application cache
+///         # let _ = id;
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait SessionHooks: Send + Sync + 'static {
+    /// Called after `create()` stored a new session, with its final ID.
+    async fn on_create(&self, _record: &Record) {}
+
+    /// Called after `save()` stored a session.
+    async fn on_save(&self, _record: &Record) {}
+
+    /// Called after `load()` found no active session with the ID `_id`.
+    async fn on_load_miss(&self, _id: &Id) {}
+
+    /// Called after `delete()`, whether or not the session existed.
+    async fn on_delete(&self, _id: &Id) {}
+
+    /// Called after a cleanup run with the number of expired sessions it deleted.
+    async fn on_expired_cleanup(&self, _deleted: u64) {}
+}
+
+/// Shared lifecycle hooks, cloned along with the store.
+#[derive(Clone)]
+pub(crate) struct LifecycleHooks(Arc<dyn SessionHooks>);
+
+impl LifecycleHooks {
+    pub(crate) fn new(hooks: Arc<dyn SessionHooks>) -> Self {
+        Self(hooks)
+    }
+
+    pub(crate) fn hooks(&self) -> &dyn SessionHooks {
+        self.0.as_ref()
+    }
+}
+
+impl fmt::Debug for LifecycleHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LifecycleHooks").finish_non_exhaustive()
+    }
+}
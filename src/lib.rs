@@ -11,9 +11,14 @@
 //! - Persistent session storage in PostgreSQL databases
 //! - Sea-ORM integration for type-safe database operations
 //! - Automatic session expiration and cleanup
+//! - Optional idle timeout for sessions that stop being written
 //! - Automatic database migration support (with `migration` feature)
 //! - Optimized upsert operations for better performance
 //! - Comprehensive error handling with dedicated error types
+//! - Optional circuit breaker to fail fast during database outages
+//! - Optional per-operation timeouts
+//! - Pluggable telemetry, with Prometheus (`prometheus` feature) and OpenTelemetry (`otel` feature) support
+//! - `#[derive(SessionStoreFromRef)]` to extract the store from Axum state (with `axum` feature)
 //! - Serialization of session data using MessagePack for compact storage
 //!
 //! ## Quick Start
@@ -66,7 +71,7 @@
 //!     .with_secure(true);
 //!
 //! // Build your Axum application with session support
-//! let app = Router::new()
+//! let app: Router = Router::new()
 //!     .route("/", get(|| async { "Hello, world!" }))
 //!     .layer(session_layer);
 //! # Ok(())
@@ -89,7 +94,7 @@
 //! let user_id: Option<u32> = session.get("user_id").await.map_err(|_| "Failed to get")?;
 //!
 //! // Remove a value
-//! session.remove("user_id").await.map_err(|_| "Failed to remove")?;
+//! session.remove::<u32>("user_id").await.map_err(|_| "Failed to remove")?;
 //!
 //! // Clear the entire session
 //! session.flush().await.map_err(|_| "Failed to flush")?;
@@ -97,10 +102,59 @@
 //! # }
 //! ```
 
+mod activity;
+#[cfg(feature = "audit-log")]
+mod audit;
+#[cfg(feature = "benchmark")]
+mod benchmark;
+mod budget;
+#[cfg(feature = "pg-only")]
+mod bulk_import;
+#[cfg(feature = "moka")]
+mod cache;
+mod circuit_breaker;
+mod cleanup;
+#[cfg(feature = "client-metadata")]
+mod client_metadata;
+#[cfg(feature = "compression")]
+mod compression;
+mod conditional;
+#[cfg(feature = "migration")]
+mod custom_migration;
+mod data_format;
+#[cfg(feature = "encryption")]
+mod encryption;
 pub mod entity;
+mod envelope;
+mod eviction;
+#[cfg(feature = "pg-only")]
+mod explain;
+mod export;
+mod failover;
+mod gc;
+#[cfg(feature = "hashed-ids")]
+mod hashed_id;
+#[cfg(feature = "pg-only")]
+mod health;
+mod hooks;
 #[cfg(feature = "migration")]
 pub mod migration;
+mod operation;
 mod postgres_store;
+mod purge;
+mod recovery;
+mod redaction;
+mod replica;
+mod retry;
+mod startup;
+mod stats;
+mod telemetry;
+#[cfg(all(feature = "pg-only", feature = "migration"))]
+mod tenant_factory;
+mod timeout;
+mod upsert;
+mod user_id;
+mod validation;
 
 pub use sea_orm;
 
@@ -111,25 +165,174 @@ pub enum SeaOrmStoreError {
     #[error(transparent)]
     SeaOrm(#[from] sea_orm::DbErr),
 
-    /// A variant to map `rmp_serde` encode errors.
+    /// A variant to map encode errors, from `rmp_serde` or from another session store.
+    #[error("{0}")]
+    Encode(String),
+
+    /// A variant to map decode errors, from `rmp_serde` or from another session store.
+    #[error("{0}")]
+    Decode(String),
+
+    /// A variant to map `serde_json` errors.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    /// The circuit breaker is open and the operation was not attempted.
+    #[error("circuit open")]
+    CircuitOpen,
+
+    /// Raw session data passed to the store was empty.
+    #[error("session data must not be empty")]
+    EmptyData,
+
+    /// A table or schema name was rejected by the table validator.
+    #[error("invalid table or schema name: {0:?}")]
+    InvalidIdentifier(String),
+
+    /// The value of a session key is larger than the configured per-key limit.
+    #[error("key '{key}' exceeds per-key size limit")]
+    KeyTooLarge {
+        /// The offending session key.
+        key: String,
+    },
+
+    /// The session table doesn't have the layout expected by the store.
+    #[error("invalid session table: {0}")]
+    InvalidSchema(String),
+
+    /// Migrations of the session schema haven't been applied yet.
+    #[error("{0} pending migrations")]
+    PendingMigrations(usize),
+
+    /// The startup check configured with `PostgresStore::with_startup_check` failed.
+    #[error("startup check failed: {0}")]
+    StartupCheck(String),
+
+    /// An operation didn't complete within its configured timeout.
+    #[error("operation timeout")]
+    Timeout,
+
+    /// A session ID was rejected by the session ID validator.
+    #[error("invalid session id")]
+    InvalidSessionId,
+
+    /// A write was attempted while the store is in read-only mode.
+    #[error("store is in read-only mode")]
+    ReadOnly,
+
+    /// The store was closed with `PostgresStore::close`.
+    #[error("store is closed")]
+    Closed,
+
+    /// A store asked for more connections than the global connection budget has left.
+    #[error("connection budget exceeded: {requested} connections requested, {available} available")]
+    ConnectionBudgetExceeded {
+        /// The maximum pool size of the store.
+        requested: u32,
+        /// The connections left in the budget.
+        available: u32,
+    },
+
+    /// A global connection budget was installed more than once.
+    #[error("a global connection budget is already installed")]
+    ConnectionBudgetAlreadyInstalled,
+
+    /// A task spawned by the store panicked or was cancelled.
     #[error(transparent)]
-    Encode(#[from] rmp_serde::encode::Error),
+    Task(#[from] tokio::task::JoinError),
+
+    /// The encryption key is invalid or its key provider failed.
+    #[error("invalid encryption key: {0}")]
+    EncryptionKey(String),
+
+    /// Session IDs were asked for while the store only persists their hashes.
+    #[error("session ids are hashed and can't be recovered")]
+    HashedSessionIds,
 
-    /// A variant to map `rmp_serde` decode errors.
+    /// Reading or writing a session dump failed.
     #[error(transparent)]
-    Decode(#[from] rmp_serde::decode::Error),
+    Io(#[from] std::io::Error),
+
+    /// A versioned save found that the session was modified or deleted since it was read.
+    #[error("session version conflict: expected version {expected}")]
+    Conflict {
+        /// The version the caller expected the stored session to have.
+        expected: u64,
+    },
+
+    /// Invalidations were subscribed to without a channel to listen on.
+    #[error("no invalidation channel configured")]
+    NoInvalidationChannel,
+
+    /// The session to copy doesn't exist or is expired.
+    #[error("session not found or expired")]
+    SessionNotFound,
+
+    /// A session with the ID to copy to already exists.
+    #[error("session already exists")]
+    SessionAlreadyExists,
 }
 
 impl From<SeaOrmStoreError> for tower_sessions::session_store::Error {
     fn from(err: SeaOrmStoreError) -> Self {
         match err {
+            // Custom errors carry a plain message, typically from `From<session_store::Error>`
+            SeaOrmStoreError::SeaOrm(sea_orm::DbErr::Custom(message)) => tower_sessions::session_store::Error::Backend(message),
             SeaOrmStoreError::SeaOrm(inner) => tower_sessions::session_store::Error::Backend(inner.to_string()),
-            SeaOrmStoreError::Decode(inner) => tower_sessions::session_store::Error::Decode(inner.to_string()),
-            SeaOrmStoreError::Encode(inner) => tower_sessions::session_store::Error::Encode(inner.to_string()),
+            SeaOrmStoreError::Decode(message) => tower_sessions::session_store::Error::Decode(message),
+            SeaOrmStoreError::Encode(message) => tower_sessions::session_store::Error::Encode(message),
+            SeaOrmStoreError::Json(inner) => tower_sessions::session_store::Error::Decode(inner.to_string()),
+            SeaOrmStoreError::CircuitOpen => tower_sessions::session_store::Error::Backend(err.to_string()),
+            SeaOrmStoreError::EmptyData => tower_sessions::session_store::Error::Encode(err.to_string()),
+            SeaOrmStoreError::InvalidIdentifier(_) => tower_sessions::session_store::Error::Backend(err.to_string()),
+            SeaOrmStoreError::KeyTooLarge { .. } => tower_sessions::session_store::Error::Encode(err.to_string()),
+            SeaOrmStoreError::InvalidSchema(_) => tower_sessions::session_store::Error::Backend(err.to_string()),
+            SeaOrmStoreError::PendingMigrations(_) => tower_sessions::session_store::Error::Backend(err.to_string()),
+            SeaOrmStoreError::StartupCheck(_) => tower_sessions::session_store::Error::Backend(err.to_string()),
+            SeaOrmStoreError::Timeout => tower_sessions::session_store::Error::Backend(err.to_string()),
+            SeaOrmStoreError::InvalidSessionId => tower_sessions::session_store::Error::Backend(err.to_string()),
+            SeaOrmStoreError::ReadOnly => tower_sessions::session_store::Error::Backend(err.to_string()),
+            SeaOrmStoreError::Closed => tower_sessions::session_store::Error::Backend(err.to_string()),
+            SeaOrmStoreError::ConnectionBudgetExceeded { .. } => tower_sessions::session_store::Error::Backend(err.to_string()),
+            SeaOrmStoreError::ConnectionBudgetAlreadyInstalled => tower_sessions::session_store::Error::Backend(err.to_string()),
+            SeaOrmStoreError::Task(inner) => tower_sessions::session_store::Error::Backend(inner.to_string()),
+            SeaOrmStoreError::EncryptionKey(_) => tower_sessions::session_store::Error::Backend(err.to_string()),
+            SeaOrmStoreError::HashedSessionIds => tower_sessions::session_store::Error::Backend(err.to_string()),
+            SeaOrmStoreError::Io(inner) => tower_sessions::session_store::Error::Backend(inner.to_string()),
+            SeaOrmStoreError::Conflict { .. } => tower_sessions::session_store::Error::Backend(err.to_string()),
+            SeaOrmStoreError::NoInvalidationChannel => tower_sessions::session_store::Error::Backend(err.to_string()),
+            SeaOrmStoreError::SessionNotFound => tower_sessions::session_store::Error::Backend(err.to_string()),
+            SeaOrmStoreError::SessionAlreadyExists => tower_sessions::session_store::Error::Backend(err.to_string()),
         }
     }
 }
 
+impl From<tower_sessions::session_store::Error> for SeaOrmStoreError {
+    /// Maps the errors of another session store, for example one wrapped by this store.
+    ///
+    /// Backend errors become `SeaOrm(DbErr::Custom(_))`. Converting the result back into a
+    /// `session_store::Error` yields the original error.
+    fn from(err: tower_sessions::session_store::Error) -> Self {
+        match err {
+            tower_sessions::session_store::Error::Backend(message) => SeaOrmStoreError::SeaOrm(sea_orm::DbErr::Custom(message)),
+            tower_sessions::session_store::Error::Encode(message) => SeaOrmStoreError::Encode(message),
+            tower_sessions::session_store::Error::Decode(message) => SeaOrmStoreError::Decode(message),
+        }
+    }
+}
+
+impl From<rmp_serde::encode::Error> for SeaOrmStoreError {
+    fn from(err: rmp_serde::encode::Error) -> Self {
+        SeaOrmStoreError::Encode(err.to_string())
+    }
+}
+
+impl From<rmp_serde::decode::Error> for SeaOrmStoreError {
+    fn from(err: rmp_serde::decode::Error) -> Self {
+        SeaOrmStoreError::Decode(err.to_string())
+    }
+}
+
 // Re-export our PostgreSQL store implementation
 /// The main PostgreSQL store implementation for tower-sessions
 ///
@@ -137,6 +340,221 @@ impl From<SeaOrmStoreError> for tower_sessions::session_store::Error {
 /// See [`PostgresStore`] documentation for usage details.
 pub use postgres_store::PostgresStore;
 
+/// The store under a backend-neutral name
+///
+/// [`PostgresStore`] works over any Sea-ORM `DatabaseConnection`: the statements are built for
+/// the backend of the connection at runtime, including how existing rows are overwritten.
+/// Methods relying on PostgreSQL features are only available with the `pg-only` feature.
+pub type SeaOrmStore = PostgresStore;
+
+/// The store for SQLite databases
+///
+/// `migrate()` creates the session table in the connected database. Timestamps are stored as
+/// RFC 3339 text in UTC, so they sort and compare correctly as text.
+///
+/// **Note**: Only available when the `sqlite` feature is enabled.
+///
+/// # Examples
+///
+/// ```no_run
+/// use sea_orm::Database;
+/// use tower_sessions_seaorm_store::SqliteStore;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let conn = Database::connect("sqlite://sessions.db?mode=rwc").await?;
+/// let store = SqliteStore::new(conn);
+/// store.migrate().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "sqlite")]
+pub type SqliteStore = PostgresStore;
+
+/// The store with an in-process cache of recently used sessions
+///
+/// **Note**: Only available when the `moka` feature is enabled.
+#[cfg(feature = "moka")]
+pub use cache::CachedPostgresStore;
+
+/// Process-wide limit on the connections opened by stores
+///
+/// Applies to stores created with [`PostgresStore::from_url_with_pool_config`].
+pub use budget::GlobalConnectionBudget;
+
+/// Circuit breaker configuration and state
+///
+/// Used with [`PostgresStore::with_circuit_breaker`] to fail fast during database outages.
+pub use circuit_breaker::{CircuitBreakerConfig, CircuitState};
+
+/// Retries on transient database errors
+///
+/// Used with [`PostgresStore::with_retry`].
+pub use retry::RetryPolicy;
+
+/// Store operation identifiers
+///
+/// Names the individual operations of the store, for example when inspecting query plans.
+pub use operation::StoreOperation;
+
+/// Description of the configured cleanup behavior
+///
+/// Returned by [`PostgresStore::gc_policy`].
+pub use gc::GarbageCollectionPolicy;
+
+/// Result of the readiness check of a store
+///
+/// Returned by [`PostgresStore::health_check`].
+///
+/// **Note**: Only available when the `pg-only` feature is enabled.
+#[cfg(feature = "pg-only")]
+pub use health::HealthReport;
+
+/// Session counts and payload sizes of a store
+///
+/// Returned by [`PostgresStore::stats`].
+pub use stats::StoreStats;
+
+/// Selection of sessions to purge
+///
+/// Used with [`PostgresStore::purge_where`].
+pub use purge::PurgeFilter;
+
+/// Policies for evicting the sessions of a user beyond the limit
+///
+/// Used with [`PostgresStore::with_max_sessions_per_user`].
+pub use eviction::EvictionPolicy;
+
+/// Handle to the background cleanup task
+///
+/// Returned by [`PostgresStore::spawn_cleanup`].
+pub use cleanup::CleanupHandle;
+
+/// Per-operation time limits
+///
+/// Used with [`PostgresStore::with_per_operation_timeout`].
+pub use timeout::OperationTimeouts;
+
+/// Conditions for atomic conditional writes
+///
+/// Used with [`PostgresStore::conditional_save`].
+pub use conditional::ConditionalSaveExpr;
+
+/// Encodings of session data
+///
+/// Used with [`PostgresStore::with_data_format`].
+pub use data_format::SessionDataFormat;
+
+/// Versions of the envelope of MessagePack session data
+///
+/// Used with [`PostgresStore::with_payload_version`].
+pub use envelope::PayloadVersion;
+
+/// Codecs of stored session data and the progress of converting between them
+///
+/// Used with [`PostgresStore::reencode_all`].
+pub use envelope::{PayloadCodec, ReencodeProgress};
+
+/// Encryption of session data at rest
+///
+/// Used with [`PostgresStore::with_encryption`].
+///
+/// **Note**: Only available when the `encryption` feature is enabled.
+#[cfg(feature = "encryption")]
+pub use encryption::{KeyProvider, KeyRing, StaticKeyProvider};
+
+/// Handling of undecodable session data
+///
+/// Used with [`PostgresStore::with_serialize_error_recovery`] and
+/// [`PostgresStore::with_fallback_deserializer`].
+pub use recovery::{DeserializeFn, FallbackDeserializer, RecoveryStrategy};
+
+/// Scrubbing of session data in logs and telemetry
+///
+/// Used with [`PostgresStore::with_session_data_redaction`].
+pub use redaction::{RedactAllValues, RedactFn, RedactKeyList};
+
+/// Analytics hooks into session activity
+///
+/// Used with [`PostgresStore::with_session_activity_tracker`].
+pub use activity::{ActivityEvent, ChannelActivityTracker, ConsolePrintActivityTracker, SessionActivityTracker};
+
+/// Async callbacks on the session lifecycle
+///
+/// Used with [`PostgresStore::with_hooks`].
+pub use hooks::SessionHooks;
+
+/// Checks run before the first operation
+///
+/// Used with [`PostgresStore::with_startup_check`].
+pub use startup::StartupCheck;
+
+/// Observability hooks for store operations
+///
+/// Used with [`PostgresStore::with_telemetry_provider`] to report operation durations and
+/// session sizes to any metrics or tracing stack.
+pub use telemetry::{LoggingTelemetryProvider, TelemetryProvider};
+
+/// Prometheus telemetry provider
+///
+/// **Note**: Only available when the `prometheus` feature is enabled.
+#[cfg(feature = "prometheus")]
+pub use telemetry::PrometheusTelemetryProvider;
+
+/// OpenTelemetry telemetry provider
+///
+/// **Note**: Only available when the `otel` feature is enabled.
+#[cfg(feature = "otel")]
+pub use telemetry::OtelTelemetryProvider;
+
+/// Benchmark configuration and results
+///
+/// Used with [`PostgresStore::benchmark_suite`].
+///
+/// **Note**: Only available when the `benchmark` feature is enabled.
+#[cfg(feature = "benchmark")]
+pub use benchmark::{BenchmarkConfig, BenchmarkResults};
+
+/// Table and schema name validation
+///
+/// Used with [`PostgresStore::with_table_validator`]; [`default_table_validator`] is applied
+/// unless another validator is configured.
+pub use validation::{default_table_validator, TableValidator};
+
+/// Session ID validation
+///
+/// Used with [`PostgresStore::with_session_id_validator`] to reject malformed IDs before they
+/// reach the database.
+pub use validation::{non_zero_id_validator, SessionIdValidator};
+
+/// Per-user session management
+///
+/// Used with [`PostgresStore::with_user_id_extractor`] to fill the `user_id` column, and
+/// returned by [`PostgresStore::sessions_for_user`].
+pub use user_id::{user_id_key, UserIdExtractor, UserSession};
+
+/// Capture of the client a session is written for
+///
+/// [`ClientMetadataLayer`] records the IP address and user agent of each request in the
+/// `ip_address` and `user_agent` columns, returned by [`PostgresStore::sessions_for_user`].
+///
+/// **Note**: Only available when the `client-metadata` feature is enabled.
+#[cfg(feature = "client-metadata")]
+pub use client_metadata::{ClientMetadata, ClientMetadataLayer, ClientMetadataService, IpExtractor};
+
+/// Stores with a PostgreSQL schema per tenant
+///
+/// **Note**: Only available when the `pg-only` and `migration` features are enabled.
+#[cfg(all(feature = "pg-only", feature = "migration"))]
+pub use tenant_factory::TenantStoreFactory;
+
+/// Typed PostgreSQL query plan
+///
+/// Returned by [`PostgresStore::explain_plan`].
+///
+/// **Note**: Only available when the `pg-only` feature is enabled.
+#[cfg(feature = "pg-only")]
+pub use explain::QueryPlan;
+
 // Re-export necessary types from tower-sessions for convenience
 /// Session storage error types and results
 ///
@@ -169,3 +587,62 @@ pub use tower_sessions::Session;
 /// Implemented by `PostgresStore` to provide the required storage functionality.
 pub use tower_sessions::SessionStore;
 
+/// Derive macro implementing Axum's `FromRef` for the store held by an application state
+///
+/// Deriving `SessionStoreFromRef` on a struct with exactly one `PostgresStore`, `SeaOrmStore` or
+/// `SqliteStore` field generates `impl FromRef<YourState> for PostgresStore`, so handlers can
+/// extract the store with `State<PostgresStore>` while the router holds the whole state. Fields
+/// typed with another alias of the store, or states holding several stores, mark the field to
+/// extract with `#[session_store]`.
+///
+/// **Note**: This derive is only available when the `axum` feature is enabled.
+///
+/// # Examples
+///
+/// ```no_run
+/// use axum::{extract::State, routing::get, Router};
+/// use tower_sessions_seaorm_store::{PostgresStore, SessionStoreFromRef};
+///
+/// #[derive(Clone, SessionStoreFromRef)]
+/// struct AppState {
+///     store: PostgresStore,
+///     app_name: String,
+/// }
+///
+/// async fn active_sessions(State(store): State<PostgresStore>) -> String {
+///     match store.count_active().await {
+///         Ok(count) => format!("{count} active sessions"),
+///         Err(err) => err.to_string(),
+///     }
+/// }
+///
+/// # fn example(state: AppState) {
+/// let app: Router = Router::new()
+///     .route("/sessions", get(active_sessions))
+///     .with_state(state);
+/// # }
+/// ```
+///
+/// Marking the store field of a state holding a store under an alias of your own:
+///
+/// ```no_run
+/// use tower_sessions_seaorm_store::{PostgresStore, SessionStoreFromRef};
+///
+/// type Sessions = PostgresStore;
+///
+/// #[derive(Clone, SessionStoreFromRef)]
+/// struct AppState {
+///     #[session_store]
+///     sessions: Sessions,
+///     archive: PostgresStore,
+/// }
+/// ```
+#[cfg(feature = "axum")]
+pub use tower_sessions_seaorm_store_macros::SessionStoreFromRef;
+
+#[cfg(feature = "axum")]
+#[doc(hidden)]
+pub mod __private {
+    // Used by the code generated by `SessionStoreFromRef`
+    pub use axum_core::extract::FromRef;
+}
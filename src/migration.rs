@@ -1,19 +1,391 @@
 pub use sea_orm_migration::prelude::*;
 
+use sea_orm::{DbBackend, Statement};
+
 mod m20240101_000001_create_session_table;
+mod m20261017_000001_add_last_accessed_at;
+mod m20261017_000002_add_version;
+#[cfg(feature = "audit-log")]
+mod m20261017_000003_create_session_audit_log;
+mod m20261017_000004_jsonb_session_data;
+mod m20261017_000005_add_user_id;
+mod m20261017_000006_add_timestamps;
+#[cfg(feature = "client-metadata")]
+mod m20261017_000007_add_client_metadata;
+mod m20261017_000008_add_tenant_id;
+mod m20261017_000009_add_deleted_at;
+#[cfg(feature = "audit-log")]
+mod m20261017_000010_protect_session_audit_log;
+mod m20261017_000011_add_data_hash;
+
+/// Converts the session data column to `JSONB`, for `SessionDataFormat::Jsonb`.
+pub use m20261017_000004_jsonb_session_data::Migration as JsonbSessionData;
 
 pub struct Migrator;
 
+/// Default schema holding the session tables on PostgreSQL.
+const DEFAULT_SCHEMA: &str = "tower_sessions";
+
+/// Default name of the session table.
+const DEFAULT_TABLE: &str = "session";
+
+/// Options of the session table created by the migrations.
+///
+/// Set with `PostgresStore::with_migration_config` or passed to [`generate_sql_with_config`].
+/// The options only apply when the table is created, so changing them for an existing table
+/// has no effect.
+///
+/// # Examples
+///
+/// ```
+/// use tower_sessions_seaorm_store::migration::MigrationConfig;
+///
+/// let config = MigrationConfig {
+///     unlogged: true,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationConfig {
+    /// Creates the session table as `UNLOGGED` on PostgreSQL.
+    ///
+    /// Writes to an unlogged table skip the write-ahead log, which makes them considerably
+    /// faster, but the table is emptied after a crash and isn't replicated to standbys. Only
+    /// use it if losing every session, i.e. logging every user out, is acceptable. An existing
+    /// table can be converted with `ALTER TABLE ... SET UNLOGGED`. Ignored by MySQL and
+    /// SQLite.
+    ///
+    /// A partitioned table can't be unlogged itself, so its partitions are created as
+    /// `UNLOGGED` instead.
+    pub unlogged: bool,
+
+    /// Creates the session table partitioned by expiry date on PostgreSQL.
+    ///
+    /// Expired sessions can then be removed by dropping whole partitions with
+    /// `PostgresStore::drop_expired_partitions`, which leaves no dead rows behind, instead of
+    /// deleting them row by row. The partitions themselves are created ahead of time with
+    /// `PostgresStore::create_partitions`, at intervals of this length; sessions expiring
+    /// outside of every partition are kept in a default partition, `<table>_default`.
+    ///
+    /// The primary key of a partitioned table has to include the expiry date, so the uniqueness
+    /// of session IDs is only enforced within a partition. The store keeps a single row per
+    /// session by moving it between partitions when its expiry date changes. The store must be
+    /// configured with the same value as when the table was created. Ignored by MySQL and
+    /// SQLite.
+    pub partition_interval: Option<PartitionInterval>,
+}
+
+/// The length of the expiry date ranges of a partitioned session table.
+///
+/// Used with [`MigrationConfig::partition_interval`]. Partitions are aligned to midnight UTC,
+/// and weekly partitions start on Mondays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PartitionInterval {
+    /// One partition per day.
+    Daily,
+    /// One partition per week.
+    Weekly,
+}
+
+impl PartitionInterval {
+    /// Returns the start of the partition holding sessions expiring at `at`.
+    #[cfg(feature = "pg-only")]
+    pub(crate) fn start(self, at: time::OffsetDateTime) -> time::OffsetDateTime {
+        let midnight = at.to_offset(time::UtcOffset::UTC).replace_time(time::Time::MIDNIGHT);
+        match self {
+            PartitionInterval::Daily => midnight,
+            PartitionInterval::Weekly => {
+                midnight - time::Duration::days(midnight.weekday().number_days_from_monday().into())
+            }
+        }
+    }
+
+    /// Returns the length of a partition.
+    #[cfg(feature = "pg-only")]
+    pub(crate) fn length(self) -> time::Duration {
+        match self {
+            PartitionInterval::Daily => time::Duration::days(1),
+            PartitionInterval::Weekly => time::Duration::weeks(1),
+        }
+    }
+}
+
+/// Returns the name of the partition of `table` starting at `start`.
+#[cfg(feature = "pg-only")]
+pub(crate) fn partition_name(table: &str, start: time::OffsetDateTime) -> String {
+    format!("{table}_p{:04}{:02}{:02}", start.year(), u8::from(start.month()), start.day())
+}
+
+/// Returns the name of the default partition of `table`.
+pub(crate) fn default_partition_name(table: &str) -> String {
+    format!("{table}_default")
+}
+
+/// The schema and session table migrations apply to.
+struct Target {
+    schema: String,
+    table: String,
+    config: MigrationConfig,
+}
+
+tokio::task_local! {
+    /// Schema and session table the migrations of the current task apply to.
+    static TARGET: Target;
+}
+
+/// Runs `migrations` on the session table `table` in `schema` instead of the default ones,
+/// creating it with `config`.
+///
+/// `MigratorTrait` has no instance to carry the names, so they are scoped to the task instead.
+pub(crate) async fn in_table<F: std::future::Future>(
+    schema: &str,
+    table: &str,
+    config: &MigrationConfig,
+    migrations: F,
+) -> F::Output {
+    let target = Target {
+        schema: schema.to_owned(),
+        table: table.to_owned(),
+        config: config.clone(),
+    };
+    TARGET.scope(target, migrations).await
+}
+
+/// Returns the schema the migrations of the current task create their tables in.
+fn schema() -> String {
+    TARGET
+        .try_with(|target| target.schema.clone())
+        .unwrap_or_else(|_| DEFAULT_SCHEMA.to_owned())
+}
+
+/// Returns the name of the session table the migrations of the current task apply to.
+fn table() -> String {
+    TARGET
+        .try_with(|target| target.table.clone())
+        .unwrap_or_else(|_| DEFAULT_TABLE.to_owned())
+}
+
+/// Returns the options the migrations of the current task create the session table with.
+fn config() -> MigrationConfig {
+    TARGET.try_with(|target| target.config.clone()).unwrap_or_default()
+}
+
+/// Returns the session table the migrations of the current task apply to.
+fn session_table() -> Alias {
+    Alias::new(table())
+}
+
+/// Returns the name of the index on `column` of the session table.
+///
+/// Index names are unique per schema on PostgreSQL, so they include the name of the table.
+fn index_name(column: &str) -> String {
+    format!("idx-{}-{column}", table())
+}
+
+/// Returns the name of the table tracking the migrations applied to the current session table.
+///
+/// The default schema and table keep `base`, so existing databases don't rerun their
+/// migrations; any other schema or table gets its own tracking table, so its migrations run
+/// even if the default table is set up.
+pub(crate) fn tracking_table(base: &str) -> Alias {
+    let mut name = base.to_owned();
+    let schema = schema();
+    if schema != DEFAULT_SCHEMA {
+        name = format!("{name}_{schema}");
+    }
+    let table = table();
+    if table != DEFAULT_TABLE {
+        name = format!("{name}_{table}");
+    }
+    Alias::new(name)
+}
+
+/// Returns `identifier` quoted as a PostgreSQL identifier.
+pub(crate) fn quoted_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// Returns `table` in the current schema on PostgreSQL.
+///
+/// MySQL and SQLite have no schemas within a database, so the table is created in the
+/// connected database there, which is where the store looks for it.
+fn table_ref(backend: DbBackend, table: impl IntoIden) -> TableRef {
+    match backend {
+        sea_orm::DbBackend::Postgres => TableRef::SchemaTable(Alias::new(schema()).into_iden(), table.into_iden()),
+        sea_orm::DbBackend::MySql | sea_orm::DbBackend::Sqlite => TableRef::Table(table.into_iden()),
+    }
+}
+
+/// Returns a column for text that is part of a key.
+///
+/// MySQL can't index `TEXT` columns without a prefix length, so a `VARCHAR(128)` is used there.
+fn key_text_column(backend: DbBackend, column: impl IntoIden) -> ColumnDef {
+    let mut column = ColumnDef::new(column);
+    match backend {
+        sea_orm::DbBackend::MySql => column.string_len(128),
+        sea_orm::DbBackend::Postgres | sea_orm::DbBackend::Sqlite => column.text(),
+    };
+    column
+}
+
+/// Returns a column for a point in time.
+///
+/// `TIMESTAMPTZ` on PostgreSQL; `DATETIME(6)` on MySQL, holding UTC times with microseconds
+/// rather than the second precision and 2038 limit of `TIMESTAMP`.
+fn timestamp_column(backend: DbBackend, column: impl IntoIden) -> ColumnDef {
+    let mut column = ColumnDef::new(column);
+    match backend {
+        sea_orm::DbBackend::MySql => column.custom(Alias::new("DATETIME(6)")),
+        sea_orm::DbBackend::Postgres | sea_orm::DbBackend::Sqlite => column.timestamp_with_time_zone(),
+    };
+    column
+}
+
+/// Returns the current time as a column default, with the precision of `timestamp_column`.
+fn current_timestamp(backend: DbBackend) -> SimpleExpr {
+    match backend {
+        // The default must have the precision of the column
+        sea_orm::DbBackend::MySql => Expr::cust("CURRENT_TIMESTAMP(6)"),
+        sea_orm::DbBackend::Postgres | sea_orm::DbBackend::Sqlite => Expr::current_timestamp().into(),
+    }
+}
+
+/// Returns the statement adding `column` to `table` unless it exists.
+///
+/// MySQL has no `ADD COLUMN IF NOT EXISTS`; the migration table already ensures the column is
+/// only added once there.
+fn add_column(backend: DbBackend, table: impl IntoIden, column: &mut ColumnDef) -> TableAlterStatement {
+    let mut statement = Table::alter().table(table_ref(backend, table)).to_owned();
+    match backend {
+        sea_orm::DbBackend::MySql => statement.add_column(column),
+        sea_orm::DbBackend::Postgres | sea_orm::DbBackend::Sqlite => statement.add_column_if_not_exists(column),
+    };
+    statement
+}
+
+/// Runs `statements` in order on the connection of `manager`.
+async fn execute(manager: &SchemaManager<'_>, statements: Vec<Statement>) -> Result<(), DbErr> {
+    for statement in statements {
+        manager.get_connection().execute(statement).await?;
+    }
+    Ok(())
+}
+
+/// Renders the statements creating the session table `table` in `schema` on `backend` as SQL.
+///
+/// The statements are those [`Migrator`] runs, without its bookkeeping, in the order it runs
+/// them, so they can be reviewed and applied by a change-management process instead of the
+/// application. No connection is needed. Like the store, MySQL and SQLite ignore `schema`.
+///
+/// On PostgreSQL, every statement only creates what doesn't exist yet, so
+/// `PostgresStore::migrate` can still run against the resulting schema later. The migration
+/// tracking table isn't part of the SQL, so on MySQL and SQLite, where columns and indexes are
+/// added unconditionally, the store must not be migrated afterwards.
+///
+/// # Parameters
+///
+/// * `backend` - The database the SQL is written for.
+/// * `table` - The name of the session table.
+/// * `schema` - The schema holding the session table, on PostgreSQL.
+///
+/// # Examples
+///
+/// ```
+/// use sea_orm::DbBackend;
+/// use tower_sessions_seaorm_store::migration::generate_sql;
+///
+/// let sql = generate_sql(DbBackend::Postgres, "session", "tower_sessions");
+/// assert!(sql.contains(r#"CREATE TABLE IF NOT EXISTS "tower_sessions"."session""#));
+/// ```
+pub fn generate_sql(backend: DbBackend, table: &str, schema: &str) -> String {
+    generate_sql_with_config(backend, table, schema, &MigrationConfig::default())
+}
+
+/// Renders the statements creating the session table `table` in `schema` with `config` on
+/// `backend` as SQL.
+///
+/// Like [`generate_sql`], with the table options of `config`.
+///
+/// # Parameters
+///
+/// * `backend` - The database the SQL is written for.
+/// * `table` - The name of the session table.
+/// * `schema` - The schema holding the session table, on PostgreSQL.
+/// * `config` - The options of the session table.
+///
+/// # Examples
+///
+/// ```
+/// use sea_orm::DbBackend;
+/// use tower_sessions_seaorm_store::migration::{generate_sql_with_config, MigrationConfig};
+///
+/// let config = MigrationConfig {
+///     unlogged: true,
+///     ..Default::default()
+/// };
+/// let sql = generate_sql_with_config(DbBackend::Postgres, "session", "tower_sessions", &config);
+/// assert!(sql.contains(r#"CREATE UNLOGGED TABLE IF NOT EXISTS "tower_sessions"."session""#));
+/// ```
+pub fn generate_sql_with_config(backend: DbBackend, table: &str, schema: &str, config: &MigrationConfig) -> String {
+    let target = Target {
+        schema: schema.to_owned(),
+        table: table.to_owned(),
+        config: config.clone(),
+    };
+    let statements = TARGET.sync_scope(target, || {
+        let mut statements = Vec::new();
+        if backend == sea_orm::DbBackend::Postgres {
+            statements.push(Statement::from_string(
+                backend,
+                format!("CREATE SCHEMA IF NOT EXISTS {}", quoted_identifier(schema)),
+            ));
+        }
+        statements.extend(m20240101_000001_create_session_table::statements(backend));
+        statements.extend(m20261017_000001_add_last_accessed_at::statements(backend));
+        statements.extend(m20261017_000002_add_version::statements(backend));
+        #[cfg(feature = "audit-log")]
+        statements.extend(m20261017_000003_create_session_audit_log::statements(backend));
+        statements.extend(m20261017_000005_add_user_id::statements(backend));
+        statements.extend(m20261017_000006_add_timestamps::statements(backend));
+        #[cfg(feature = "client-metadata")]
+        statements.extend(m20261017_000007_add_client_metadata::statements(backend));
+        statements.extend(m20261017_000008_add_tenant_id::statements(backend));
+        statements.extend(m20261017_000009_add_deleted_at::statements(backend));
+        #[cfg(feature = "audit-log")]
+        statements.extend(m20261017_000010_protect_session_audit_log::statements(backend));
+        statements.extend(m20261017_000011_add_data_hash::statements(backend));
+        statements
+    });
+
+    statements
+        .iter()
+        .map(|statement| format!("{statement};\n"))
+        .collect()
+}
+
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     // Override the name of migration table to avoid conflicts
     fn migration_table_name() -> sea_orm::DynIden {
-        Alias::new("tower_sessions_seaorm_migrations").into_iden()
+        tracking_table("tower_sessions_seaorm_migrations").into_iden()
     }
 
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
         vec![
             Box::new(m20240101_000001_create_session_table::Migration),
+            Box::new(m20261017_000001_add_last_accessed_at::Migration),
+            Box::new(m20261017_000002_add_version::Migration),
+            #[cfg(feature = "audit-log")]
+            Box::new(m20261017_000003_create_session_audit_log::Migration),
+            Box::new(m20261017_000005_add_user_id::Migration),
+            Box::new(m20261017_000006_add_timestamps::Migration),
+            #[cfg(feature = "client-metadata")]
+            Box::new(m20261017_000007_add_client_metadata::Migration),
+            Box::new(m20261017_000008_add_tenant_id::Migration),
+            Box::new(m20261017_000009_add_deleted_at::Migration),
+            #[cfg(feature = "audit-log")]
+            Box::new(m20261017_000010_protect_session_audit_log::Migration),
+            Box::new(m20261017_000011_add_data_hash::Migration),
         ]
     }
 }
\ No newline at end of file
@@ -1,84 +1,144 @@
 use sea_orm_migration::prelude::*;
 
+use sea_orm::{DbBackend, Statement};
+
+use super::{
+    config, default_partition_name, execute, index_name, key_text_column, quoted_identifier, schema, session_table,
+    table, table_ref, timestamp_column,
+};
+
 #[derive(DeriveMigrationName)]
 pub struct Migration;
 
 #[async_trait::async_trait]
 impl MigrationTrait for Migration {
     async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
-        // Create the tower_sessions schema if it doesn't exist (PostgreSQL specific)
+        // Create the schema if it doesn't exist (PostgreSQL specific)
         if manager.get_database_backend() == sea_orm::DbBackend::Postgres {
-            let create_schema_query = r#"CREATE SCHEMA IF NOT EXISTS "tower_sessions""#;
-            
-            // Concurrent create schema may fail due to duplicate key violations.
-            // This works around that by assuming the schema must exist on such an error.
-            if let Err(err) = manager.get_connection().execute_unprepared(create_schema_query).await {
-                if !err.to_string().contains("duplicate key value violates unique constraint") {
-                    return Err(err);
-                }
-            }
+            create_schema(manager).await?;
         }
 
-        // Create the session table in the tower_sessions schema
-        manager
-            .create_table(
-                Table::create()
-                    .table((Alias::new("tower_sessions"), Session::Table))
-                    .if_not_exists()
-                    .col(
-                        ColumnDef::new(Session::Id)
-                            .text()
-                            .not_null()
-                            .primary_key(),
-                    )
-                    .col(
-                        ColumnDef::new(Session::Data)
-                            .binary()
-                            .not_null(),
-                    )
-                    .col(
-                        ColumnDef::new(Session::ExpiryDate)
-                            .timestamp_with_time_zone()
-                            .not_null(),
-                    )
-                    .to_owned(),
-            )
-            .await?;
+        execute(manager, statements(manager.get_database_backend())).await
+    }
 
-        // Create an index on expiry_date for efficient cleanup
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Drop the session table with its index; the schema may hold other tables, so it stays
         manager
-            .create_index(
-                Index::create()
-                    .if_not_exists()
-                    .name("idx-session-expiry_date")
-                    .table((Alias::new("tower_sessions"), Session::Table))
-                    .col(Session::ExpiryDate)
+            .drop_table(
+                Table::drop()
+                    .table(table_ref(manager.get_database_backend(), session_table()))
+                    .if_exists()
                     .to_owned(),
             )
             .await?;
 
         Ok(())
     }
+}
 
-    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
-        // Drop the index first
-        manager
-            .drop_index(Index::drop().name("idx-session-expiry_date").to_owned())
-            .await?;
+/// Returns the statements creating the session table and its index on `backend`.
+pub(super) fn statements(backend: DbBackend) -> Vec<Statement> {
+    // `binary()` is a single byte on MySQL, so the data goes to a blob there
+    let mut data = ColumnDef::new(Session::Data);
+    match backend {
+        sea_orm::DbBackend::MySql => data.custom(Alias::new("LONGBLOB")),
+        sea_orm::DbBackend::Postgres | sea_orm::DbBackend::Sqlite => data.binary(),
+    };
 
-        // Drop the session table from the tower_sessions schema
-        manager
-            .drop_table(Table::drop().table((Alias::new("tower_sessions"), Session::Table)).to_owned())
-            .await?;
+    let config = config();
+    let partitioned = backend == sea_orm::DbBackend::Postgres && config.partition_interval.is_some();
 
-        Ok(())
+    // Create the session table in the schema
+    let mut id = key_text_column(backend, Session::Id);
+    id.not_null();
+    if !partitioned {
+        id.primary_key();
+    }
+    let mut create = Table::create();
+    create
+        .table(table_ref(backend, session_table()))
+        .if_not_exists()
+        .col(&mut id)
+        .col(data.not_null())
+        .col(
+            timestamp_column(backend, Session::ExpiryDate)
+                .not_null(),
+        );
+    if partitioned {
+        // The primary key of a partitioned table must include the partition key
+        create.primary_key(Index::create().col(Session::Id).col(Session::ExpiryDate));
     }
+    let mut create_table = backend.build(&create);
+
+    let mut statements = Vec::new();
+    if partitioned {
+        // sea-query has no partitioned tables
+        create_table.sql.push_str(r#" PARTITION BY RANGE ("expiry_date")"#);
+        statements.push(create_table);
+        statements.push(Statement::from_string(
+            backend,
+            format!(
+                "CREATE {}TABLE IF NOT EXISTS {}.{} PARTITION OF {}.{} DEFAULT",
+                if config.unlogged { "UNLOGGED " } else { "" },
+                quoted_identifier(&schema()),
+                quoted_identifier(&default_partition_name(&table())),
+                quoted_identifier(&schema()),
+                quoted_identifier(&table()),
+            ),
+        ));
+    } else {
+        // sea-query has no unlogged tables
+        if backend == sea_orm::DbBackend::Postgres && config.unlogged {
+            create_table.sql = create_table.sql.replacen("CREATE TABLE", "CREATE UNLOGGED TABLE", 1);
+        }
+        statements.push(create_table);
+    }
+
+    // Create an index on expiry_date for efficient cleanup
+    statements.push(backend.build(
+        Index::create()
+            .if_not_exists()
+            .name(index_name("expiry_date"))
+            .table(table_ref(backend, session_table()))
+            .col(Session::ExpiryDate),
+    ));
+    statements
 }
 
 #[derive(DeriveIden)]
 enum Session {
-    Table,
     Id,
     Data,
     ExpiryDate,
-}
\ No newline at end of file
+}
+
+/// Creates the schema of the session table unless it exists.
+///
+/// `CREATE SCHEMA IF NOT EXISTS` still requires the `CREATE` privilege on the database, which
+/// managed PostgreSQL setups often withhold, so an existing schema such as `public` is skipped.
+async fn create_schema(manager: &SchemaManager<'_>) -> Result<(), DbErr> {
+    let schema = schema();
+    let conn = manager.get_connection();
+    let exists = conn
+        .query_one(Statement::from_sql_and_values(
+            sea_orm::DbBackend::Postgres,
+            "SELECT 1 FROM pg_namespace WHERE nspname = $1",
+            [schema.clone().into()],
+        ))
+        .await?
+        .is_some();
+    if exists {
+        return Ok(());
+    }
+
+    // Concurrent create schema may fail due to duplicate key violations.
+    // This works around that by assuming the schema must exist on such an error.
+    let create_schema_query = format!("CREATE SCHEMA IF NOT EXISTS {}", quoted_identifier(&schema));
+    if let Err(err) = conn.execute_unprepared(&create_schema_query).await {
+        if !err.to_string().contains("duplicate key value violates unique constraint") {
+            return Err(err);
+        }
+    }
+
+    Ok(())
+}
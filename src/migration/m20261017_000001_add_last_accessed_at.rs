@@ -0,0 +1,67 @@
+use sea_orm_migration::prelude::*;
+
+use sea_orm::{DbBackend, Statement};
+
+use super::{add_column, current_timestamp, execute, index_name, session_table, table_ref, timestamp_column};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        execute(manager, statements(manager.get_database_backend())).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+
+        // Drop the index first
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(index_name("last_accessed_at"))
+                    .table(table_ref(backend, session_table()))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(table_ref(backend, session_table()))
+                    .drop_column(Session::LastAccessedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Returns the statements adding the `last_accessed_at` column and its index on `backend`.
+pub(super) fn statements(backend: DbBackend) -> Vec<Statement> {
+    vec![
+        // Track the last write so the store can expire idle sessions
+        backend.build(&add_column(
+            backend,
+            session_table(),
+            timestamp_column(backend, Session::LastAccessedAt)
+                .not_null()
+                .default(current_timestamp(backend)),
+        )),
+        // Create an index on last_accessed_at for efficient idle cleanup
+        backend.build(
+            Index::create()
+                .if_not_exists()
+                .name(index_name("last_accessed_at"))
+                .table(table_ref(backend, session_table()))
+                .col(Session::LastAccessedAt),
+        ),
+    ]
+}
+
+#[derive(DeriveIden)]
+enum Session {
+    LastAccessedAt,
+}
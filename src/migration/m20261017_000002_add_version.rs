@@ -0,0 +1,46 @@
+use sea_orm_migration::prelude::*;
+
+use sea_orm::{DbBackend, Statement};
+
+use super::{add_column, execute, session_table, table_ref};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        execute(manager, statements(manager.get_database_backend())).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(table_ref(manager.get_database_backend(), session_table()))
+                    .drop_column(Session::Version)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Returns the statement adding the `version` column on `backend`.
+pub(super) fn statements(backend: DbBackend) -> Vec<Statement> {
+    // Count the writes to each session for optimistic locking
+    vec![backend.build(&add_column(
+        backend,
+        session_table(),
+        ColumnDef::new(Session::Version)
+            .big_integer()
+            .not_null()
+            .default(0),
+    ))]
+}
+
+#[derive(DeriveIden)]
+enum Session {
+    Version,
+}
@@ -0,0 +1,80 @@
+use sea_orm_migration::prelude::*;
+
+use sea_orm::{DbBackend, Statement};
+
+use super::{current_timestamp, execute, key_text_column, table, table_ref, timestamp_column, DEFAULT_TABLE};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        execute(manager, statements(manager.get_database_backend())).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The audit log of a schema is shared by all its session tables, so it goes with the
+        // default one only
+        if table() != DEFAULT_TABLE {
+            return Ok(());
+        }
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(table_ref(manager.get_database_backend(), SessionAuditLog::Table))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Returns the statements creating the audit log table and its index on `backend`.
+pub(super) fn statements(backend: DbBackend) -> Vec<Statement> {
+    vec![
+        // Record every session operation for the audit trail
+        backend.build(
+            Table::create()
+                .table(table_ref(backend, SessionAuditLog::Table))
+                .if_not_exists()
+                .col(
+                    ColumnDef::new(SessionAuditLog::Id)
+                        .big_integer()
+                        .not_null()
+                        .auto_increment()
+                        .primary_key(),
+                )
+                .col(key_text_column(backend, SessionAuditLog::SessionId).not_null())
+                .col(ColumnDef::new(SessionAuditLog::Operation).text().not_null())
+                .col(
+                    timestamp_column(backend, SessionAuditLog::OccurredAt)
+                        .not_null()
+                        .default(current_timestamp(backend)),
+                )
+                .col(ColumnDef::new(SessionAuditLog::Actor).text())
+                .col(ColumnDef::new(SessionAuditLog::DataHash).text()),
+        ),
+        // Look up the history of a session
+        backend.build(
+            Index::create()
+                .if_not_exists()
+                .name("idx-session_audit_log-session_id")
+                .table(table_ref(backend, SessionAuditLog::Table))
+                .col(SessionAuditLog::SessionId),
+        ),
+    ]
+}
+
+#[derive(DeriveIden)]
+enum SessionAuditLog {
+    Table,
+    Id,
+    SessionId,
+    Operation,
+    OccurredAt,
+    Actor,
+    DataHash,
+}
@@ -0,0 +1,45 @@
+use sea_orm_migration::prelude::*;
+
+use super::{quoted_identifier, schema, table};
+
+/// Converts the `data` column of the session table from `BYTEA` to `JSONB`.
+///
+/// Not part of `Migrator`: the store runs it from `migrate()` once
+/// `SessionDataFormat::Jsonb` is configured, and records it with the custom migrations. Rows
+/// holding MessagePack data can't be converted in SQL, so the migration fails if the table
+/// already contains any; delete them first.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != sea_orm::DbBackend::Postgres {
+            return Err(DbErr::Migration("JSONB session data requires PostgreSQL".to_string()));
+        }
+
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                r#"ALTER TABLE {}.{} ALTER COLUMN "data" TYPE JSONB USING convert_from("data", 'UTF8')::jsonb"#,
+                quoted_identifier(&schema()),
+                quoted_identifier(&table()),
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                r#"ALTER TABLE {}.{} ALTER COLUMN "data" TYPE BYTEA USING convert_to("data"::text, 'UTF8')"#,
+                quoted_identifier(&schema()),
+                quoted_identifier(&table()),
+            ))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+
+use sea_orm::{DbBackend, Statement};
+
+use super::{add_column, execute, index_name, key_text_column, session_table, table_ref};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        execute(manager, statements(manager.get_database_backend())).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+
+        // SQLite can't drop an indexed column
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(index_name("user_id"))
+                    .table(table_ref(backend, session_table()))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(table_ref(backend, session_table()))
+                    .drop_column(Session::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Returns the statements adding the `user_id` column and its index on `backend`.
+pub(super) fn statements(backend: DbBackend) -> Vec<Statement> {
+    vec![
+        // Owner of the session, as extracted from its data on write
+        backend.build(&add_column(backend, session_table(), key_text_column(backend, Session::UserId).null())),
+        // Sessions are looked up by user to log them out everywhere
+        backend.build(
+            Index::create()
+                .if_not_exists()
+                .name(index_name("user_id"))
+                .table(table_ref(backend, session_table()))
+                .col(Session::UserId),
+        ),
+    ]
+}
+
+#[derive(DeriveIden)]
+enum Session {
+    UserId,
+}
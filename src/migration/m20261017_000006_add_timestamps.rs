@@ -0,0 +1,66 @@
+use sea_orm_migration::prelude::*;
+
+use sea_orm::{DbBackend, Statement};
+
+use super::{add_column, execute, index_name, session_table, table_ref, timestamp_column};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        execute(manager, statements(manager.get_database_backend())).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+
+        // SQLite can't drop an indexed column
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(index_name("created_at"))
+                    .table(table_ref(backend, session_table()))
+                    .to_owned(),
+            )
+            .await?;
+
+        // SQLite only drops one column per statement
+        for column in [Session::CreatedAt, Session::UpdatedAt] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table_ref(backend, session_table()))
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the statements adding the `created_at` and `updated_at` columns on `backend`.
+pub(super) fn statements(backend: DbBackend) -> Vec<Statement> {
+    vec![
+        // Existing sessions keep NULL, their creation time is unknown
+        backend.build(&add_column(backend, session_table(), timestamp_column(backend, Session::CreatedAt).null())),
+        backend.build(&add_column(backend, session_table(), timestamp_column(backend, Session::UpdatedAt).null())),
+        // Retention reports select sessions by age
+        backend.build(
+            Index::create()
+                .if_not_exists()
+                .name(index_name("created_at"))
+                .table(table_ref(backend, session_table()))
+                .col(Session::CreatedAt),
+        ),
+    ]
+}
+
+#[derive(DeriveIden)]
+enum Session {
+    CreatedAt,
+    UpdatedAt,
+}
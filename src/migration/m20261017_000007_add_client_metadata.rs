@@ -0,0 +1,66 @@
+use sea_orm_migration::prelude::*;
+
+use sea_orm::{DbBackend, Statement};
+
+use super::{add_column, execute, index_name, key_text_column, session_table, table_ref};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        execute(manager, statements(manager.get_database_backend())).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+
+        // SQLite can't drop an indexed column
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(index_name("ip_address"))
+                    .table(table_ref(backend, session_table()))
+                    .to_owned(),
+            )
+            .await?;
+
+        // SQLite only drops one column per statement
+        for column in [Session::IpAddress, Session::UserAgent] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table_ref(backend, session_table()))
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the statements adding the `ip_address` and `user_agent` columns on `backend`.
+pub(super) fn statements(backend: DbBackend) -> Vec<Statement> {
+    vec![
+        // Client of the last request that wrote the session
+        backend.build(&add_column(backend, session_table(), key_text_column(backend, Session::IpAddress).null())),
+        backend.build(&add_column(backend, session_table(), ColumnDef::new(Session::UserAgent).text().null())),
+        // Forensic lookups find the sessions used from an address
+        backend.build(
+            Index::create()
+                .if_not_exists()
+                .name(index_name("ip_address"))
+                .table(table_ref(backend, session_table()))
+                .col(Session::IpAddress),
+        ),
+    ]
+}
+
+#[derive(DeriveIden)]
+enum Session {
+    IpAddress,
+    UserAgent,
+}
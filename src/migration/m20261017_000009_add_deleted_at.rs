@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+
+use sea_orm::{DbBackend, Statement};
+
+use super::{add_column, execute, index_name, session_table, table_ref, timestamp_column};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        execute(manager, statements(manager.get_database_backend())).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+
+        // SQLite can't drop an indexed column
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(index_name("deleted_at"))
+                    .table(table_ref(backend, session_table()))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(table_ref(backend, session_table()))
+                    .drop_column(Session::DeletedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Returns the statements adding the `deleted_at` column and its index on `backend`.
+pub(super) fn statements(backend: DbBackend) -> Vec<Statement> {
+    vec![
+        // Time the session was deleted, set instead of removing the row in soft delete mode
+        backend.build(&add_column(backend, session_table(), timestamp_column(backend, Session::DeletedAt).null())),
+        // Cleanup purges soft-deleted sessions by deletion time
+        backend.build(
+            Index::create()
+                .if_not_exists()
+                .name(index_name("deleted_at"))
+                .table(table_ref(backend, session_table()))
+                .col(Session::DeletedAt),
+        ),
+    ]
+}
+
+#[derive(DeriveIden)]
+enum Session {
+    DeletedAt,
+}
@@ -0,0 +1,113 @@
+use sea_orm_migration::prelude::*;
+
+use sea_orm::{DbBackend, Statement};
+
+use super::{execute, quoted_identifier, schema, table, DEFAULT_TABLE};
+
+/// Name of the triggers rejecting changes to the audit log, and of their function on
+/// PostgreSQL.
+const TRIGGER: &str = "session_audit_log_append_only";
+
+/// Error raised by the triggers.
+const MESSAGE: &str = "the session audit log is append-only";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        execute(manager, statements(manager.get_database_backend())).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Like the audit log itself, the triggers go with the default session table only
+        if table() != DEFAULT_TABLE {
+            return Ok(());
+        }
+
+        let statements = match manager.get_database_backend() {
+            DbBackend::Postgres => vec![
+                format!("DROP TRIGGER IF EXISTS {TRIGGER} ON {}", audit_log()),
+                format!("DROP TRIGGER IF EXISTS {TRIGGER}_truncate ON {}", audit_log()),
+                format!("DROP FUNCTION IF EXISTS {}", function()),
+            ],
+            DbBackend::MySql | DbBackend::Sqlite => vec![
+                format!("DROP TRIGGER IF EXISTS {TRIGGER}_update"),
+                format!("DROP TRIGGER IF EXISTS {TRIGGER}_delete"),
+            ],
+        };
+        for sql in statements {
+            manager.get_connection().execute_unprepared(&sql).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the statements making the audit log append-only on `backend`.
+///
+/// Entries are only ever inserted, so triggers reject every `UPDATE` and `DELETE` of the table,
+/// and `TRUNCATE` on PostgreSQL, keeping the trail of session operations intact. The triggers
+/// are replaced if they exist, since every session table of a schema shares the audit log.
+pub(super) fn statements(backend: DbBackend) -> Vec<Statement> {
+    let sql = match backend {
+        DbBackend::Postgres => vec![
+            format!(
+                "CREATE OR REPLACE FUNCTION {}() RETURNS trigger LANGUAGE plpgsql AS $$ BEGIN RAISE EXCEPTION '{MESSAGE}'; END $$",
+                function(),
+            ),
+            format!("DROP TRIGGER IF EXISTS {TRIGGER} ON {}", audit_log()),
+            format!(
+                "CREATE TRIGGER {TRIGGER} BEFORE UPDATE OR DELETE ON {} FOR EACH ROW EXECUTE FUNCTION {}()",
+                audit_log(),
+                function(),
+            ),
+            format!("DROP TRIGGER IF EXISTS {TRIGGER}_truncate ON {}", audit_log()),
+            format!(
+                "CREATE TRIGGER {TRIGGER}_truncate BEFORE TRUNCATE ON {} FOR EACH STATEMENT EXECUTE FUNCTION {}()",
+                audit_log(),
+                function(),
+            ),
+        ],
+        // MySQL and SQLite triggers handle a single kind of statement each
+        DbBackend::MySql => ["UPDATE", "DELETE"]
+            .into_iter()
+            .flat_map(|event| {
+                let name = format!("{TRIGGER}_{}", event.to_lowercase());
+                [
+                    format!("DROP TRIGGER IF EXISTS {name}"),
+                    format!(
+                        "CREATE TRIGGER {name} BEFORE {event} ON session_audit_log FOR EACH ROW \
+                         SIGNAL SQLSTATE '45000' SET MESSAGE_TEXT = '{MESSAGE}'"
+                    ),
+                ]
+            })
+            .collect(),
+        DbBackend::Sqlite => ["UPDATE", "DELETE"]
+            .into_iter()
+            .flat_map(|event| {
+                let name = format!("{TRIGGER}_{}", event.to_lowercase());
+                [
+                    format!("DROP TRIGGER IF EXISTS {name}"),
+                    format!(
+                        "CREATE TRIGGER {name} BEFORE {event} ON session_audit_log \
+                         BEGIN SELECT RAISE(ABORT, '{MESSAGE}'); END"
+                    ),
+                ]
+            })
+            .collect(),
+    };
+
+    sql.into_iter().map(|sql| Statement::from_string(backend, sql)).collect()
+}
+
+/// Returns the audit log table in the current schema, quoted for PostgreSQL.
+fn audit_log() -> String {
+    format!("{}.{}", quoted_identifier(&schema()), quoted_identifier("session_audit_log"))
+}
+
+/// Returns the trigger function in the current schema, quoted for PostgreSQL.
+fn function() -> String {
+    format!("{}.{}", quoted_identifier(&schema()), quoted_identifier(TRIGGER))
+}
@@ -0,0 +1,39 @@
+//! Identifiers for the operations performed by the session store.
+
+use std::fmt;
+
+/// A session store operation.
+///
+/// Used to select which query to inspect with `PostgresStore::explain_plan`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StoreOperation {
+    /// Inserting a new session (`SessionStore::create`).
+    Create,
+    /// Writing an existing session (`SessionStore::save`).
+    Save,
+    /// Reading a session (`SessionStore::load`).
+    Load,
+    /// Removing a session (`SessionStore::delete`).
+    Delete,
+    /// Removing all expired sessions (`ExpiredDeletion::delete_expired`).
+    DeleteExpired,
+}
+
+impl StoreOperation {
+    /// Returns the name of the operation in `snake_case`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StoreOperation::Create => "create",
+            StoreOperation::Save => "save",
+            StoreOperation::Load => "load",
+            StoreOperation::Delete => "delete",
+            StoreOperation::DeleteExpired => "delete_expired",
+        }
+    }
+}
+
+impl fmt::Display for StoreOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
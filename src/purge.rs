@@ -0,0 +1,36 @@
+//! Selection of the sessions removed by `PostgresStore::purge_where`.
+
+use sea_orm::Condition;
+
+/// The sessions `PostgresStore::purge_where` removes, e.g. those of a data subject asking to
+/// be forgotten.
+///
+/// A [`Condition`] converts into a filter, so it can be passed to `purge_where` directly.
+///
+/// # Examples
+///
+/// ```
+/// use sea_orm::{ColumnTrait, Condition};
+/// use tower_sessions_seaorm_store::entity::session;
+/// use tower_sessions_seaorm_store::PurgeFilter;
+///
+/// let by_user = PurgeFilter::UserIds(vec!["42".to_string()]);
+/// let by_condition: PurgeFilter = Condition::all().add(session::Column::UserId.starts_with("tmp-")).into();
+/// ```
+#[derive(Debug, Clone)]
+pub enum PurgeFilter {
+    /// Sessions matching a condition on the columns of the session table, such as those of
+    /// [`crate::entity::session::Column`].
+    Condition(Condition),
+    /// Sessions owned by any of the users, matched on the `user_id` column.
+    ///
+    /// The column is only filled when a user ID extractor is configured with
+    /// `PostgresStore::with_user_id_extractor`. An empty list matches no session.
+    UserIds(Vec<String>),
+}
+
+impl From<Condition> for PurgeFilter {
+    fn from(condition: Condition) -> Self {
+        PurgeFilter::Condition(condition)
+    }
+}
@@ -0,0 +1,57 @@
+//! Recovery from session data that can't be decoded.
+
+use std::fmt;
+use std::sync::Arc;
+
+use tower_sessions::session::Record;
+
+/// What `load()` does when the stored session data can't be decoded.
+///
+/// Session data may become undecodable when a row is corrupted or was written by an
+/// incompatible version of the application. Configure the strategy with
+/// `PostgresStore::with_serialize_error_recovery`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum RecoveryStrategy {
+    /// Return a `session_store::Error::Decode` error (the default).
+    #[default]
+    Fail,
+    /// Treat the session as missing, so the caller starts a fresh session.
+    ReturnNone,
+    /// Delete the undecodable row and treat the session as missing.
+    DeleteAndReturnNone,
+}
+
+/// A function decoding session data, used by [`FallbackDeserializer::Custom`].
+pub type DeserializeFn = Arc<dyn Fn(&[u8]) -> Result<Record, String> + Send + Sync>;
+
+/// A secondary decoder for session data that isn't valid MessagePack.
+///
+/// Configured with `PostgresStore::with_fallback_deserializer`, it is tried whenever decoding
+/// a session with MessagePack fails, typically for sessions written in a legacy format. If it
+/// fails too, the configured [`RecoveryStrategy`] applies.
+#[derive(Clone)]
+pub enum FallbackDeserializer {
+    /// Decode the session data as a JSON-encoded `Record`.
+    Json,
+    /// Decode the session data with a custom function.
+    Custom(DeserializeFn),
+}
+
+impl FallbackDeserializer {
+    /// Decodes `data` with the fallback format.
+    pub(crate) fn deserialize(&self, data: &[u8]) -> Result<Record, String> {
+        match self {
+            FallbackDeserializer::Json => serde_json::from_slice(data).map_err(|err| err.to_string()),
+            FallbackDeserializer::Custom(deserialize) => deserialize(data),
+        }
+    }
+}
+
+impl fmt::Debug for FallbackDeserializer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FallbackDeserializer::Json => f.write_str("Json"),
+            FallbackDeserializer::Custom(_) => f.debug_tuple("Custom").finish_non_exhaustive(),
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Scrubbing of session data before it reaches logs and telemetry.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use serde_json::{Map, Value};
+
+/// A function rewriting session data before it is passed to the telemetry provider.
+///
+/// It receives the session data as a JSON object mapping session keys to their values.
+pub type RedactFn = Arc<dyn Fn(Value) -> Value + Send + Sync>;
+
+/// The text replacing redacted values.
+const REDACTED: &str = "<redacted>";
+
+/// A redactor replacing every leaf value with `"<redacted>"`.
+///
+/// Objects and arrays are kept, so the shape of the session data, including its keys, stays
+/// visible. Convert it into a [`RedactFn`] with `into()`.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+/// use tower_sessions_seaorm_store::{RedactAllValues, RedactFn};
+///
+/// let redact: RedactFn = RedactAllValues.into();
+/// assert_eq!(
+///     redact(json!({ "user": { "email": "jane@example.com", "roles": ["admin"] } })),
+///     json!({ "user": { "email": "<redacted>", "roles": ["<redacted>"] } }),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RedactAllValues;
+
+impl RedactAllValues {
+    /// Returns `value` with every leaf value replaced.
+    pub fn redact(&self, value: Value) -> Value {
+        match value {
+            Value::Object(object) => Value::Object(
+                object
+                    .into_iter()
+                    .map(|(key, value)| (key, self.redact(value)))
+                    .collect(),
+            ),
+            Value::Array(array) => Value::Array(array.into_iter().map(|value| self.redact(value)).collect()),
+            _ => Value::String(REDACTED.to_owned()),
+        }
+    }
+}
+
+impl From<RedactAllValues> for RedactFn {
+    fn from(redactor: RedactAllValues) -> Self {
+        Arc::new(move |value| redactor.redact(value))
+    }
+}
+
+/// A redactor replacing the values of the listed keys with `"<redacted>"`.
+///
+/// Keys are matched at any depth, so nested objects are scrubbed too. Other values are left
+/// untouched. Convert it into a [`RedactFn`] with `into()`.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+/// use tower_sessions_seaorm_store::{RedactFn, RedactKeyList};
+///
+/// let redact: RedactFn = RedactKeyList(vec!["email".to_string()]).into();
+/// assert_eq!(
+///     redact(json!({ "theme": "dark", "user": { "email": "jane@example.com" } })),
+///     json!({ "theme": "dark", "user": { "email": "<redacted>" } }),
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RedactKeyList(pub Vec<String>);
+
+impl RedactKeyList {
+    /// Returns `value` with the values of the listed keys replaced.
+    pub fn redact(&self, value: Value) -> Value {
+        match value {
+            Value::Object(object) => Value::Object(
+                object
+                    .into_iter()
+                    .map(|(key, value)| {
+                        if self.0.contains(&key) {
+                            (key, Value::String(REDACTED.to_owned()))
+                        } else {
+                            (key, self.redact(value))
+                        }
+                    })
+                    .collect(),
+            ),
+            Value::Array(array) => Value::Array(array.into_iter().map(|value| self.redact(value)).collect()),
+            value => value,
+        }
+    }
+}
+
+impl From<RedactKeyList> for RedactFn {
+    fn from(redactor: RedactKeyList) -> Self {
+        Arc::new(move |value| redactor.redact(value))
+    }
+}
+
+/// The session data redactor of a store, if any, cloned along with the store.
+#[derive(Clone, Default)]
+pub(crate) struct RedactionHandle(Option<RedactFn>);
+
+impl RedactionHandle {
+    pub(crate) fn new(redact: RedactFn) -> Self {
+        Self(Some(redact))
+    }
+
+    /// Converts session data into a JSON object and redacts it, if a redactor is configured.
+    pub(crate) fn redact(&self, data: &HashMap<String, Value>) -> Value {
+        let object: Map<String, Value> = data.iter().map(|(key, value)| (key.clone(), value.clone())).collect();
+        let value = Value::Object(object);
+
+        match &self.0 {
+            Some(redact) => redact(value),
+            None => value,
+        }
+    }
+}
+
+impl fmt::Debug for RedactionHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(_) => f.debug_tuple("Redaction").finish_non_exhaustive(),
+            None => f.write_str("None"),
+        }
+    }
+}
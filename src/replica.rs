@@ -0,0 +1,33 @@
+//! Read replicas serving session loads.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use sea_orm::DatabaseConnection;
+
+/// Read replicas of the primary database, used in turn and shared by all clones of the store.
+#[derive(Debug, Clone)]
+pub(crate) struct ReadReplicas {
+    connections: Arc<[DatabaseConnection]>,
+    next: Arc<AtomicUsize>,
+}
+
+impl ReadReplicas {
+    /// Returns the replicas of `connections`, or `None` if there are none.
+    pub(crate) fn new(connections: Vec<DatabaseConnection>) -> Option<Self> {
+        if connections.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            connections: connections.into(),
+            next: Arc::default(),
+        })
+    }
+
+    /// Returns the replica the next read goes to, in round-robin order.
+    pub(crate) fn next(&self) -> &DatabaseConnection {
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.connections.len();
+        &self.connections[index]
+    }
+}
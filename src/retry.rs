@@ -0,0 +1,61 @@
+//! Retries of store operations failing with transient database errors.
+
+use std::time::Duration;
+
+use sea_orm::DbErr;
+
+/// Retry policy installed by `PostgresStore::with_retry`.
+///
+/// The first retry waits `initial_backoff`, and every further retry waits twice as long as the
+/// previous one, up to `max_backoff`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt; 0 disables retries.
+    pub max_retries: u32,
+    /// Wait before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper bound of the wait between two attempts.
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Returns the wait before retry number `retry`, counted from 0.
+    pub(crate) fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff)
+    }
+}
+
+/// Returns `true` if `err` is likely to go away when the statement is run again.
+///
+/// Covers failures to get or keep a connection, serialization failures and deadlocks, and
+/// SQLite's busy and locked errors.
+pub(crate) fn is_transient(err: &DbErr) -> bool {
+    match err {
+        DbErr::ConnectionAcquire(_) => true,
+        #[cfg(any(feature = "postgres", feature = "sqlite", feature = "mysql"))]
+        DbErr::Conn(sea_orm::RuntimeErr::SqlxError(err))
+        | DbErr::Exec(sea_orm::RuntimeErr::SqlxError(err))
+        | DbErr::Query(sea_orm::RuntimeErr::SqlxError(err)) => is_transient_sqlx_error(err),
+        _ => false,
+    }
+}
+
+#[cfg(any(feature = "postgres", feature = "sqlite", feature = "mysql"))]
+fn is_transient_sqlx_error(err: &sea_orm::sqlx::Error) -> bool {
+    use sea_orm::sqlx::Error;
+
+    match err {
+        Error::Io(_) | Error::PoolTimedOut => true,
+        Error::Database(err) => matches!(
+            err.code().as_deref(),
+            // SQLSTATE: serialization failure, deadlock, connection exceptions, server shutting
+            // down or not accepting connections yet, too many connections
+            Some("40001" | "40P01" | "08000" | "08001" | "08003" | "08004" | "08006" | "57P01" | "57P03" | "53300")
+                // SQLite result codes: busy, locked, busy recovery, busy snapshot
+                | Some("5" | "6" | "261" | "517")
+        ),
+        _ => false,
+    }
+}
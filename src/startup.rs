@@ -0,0 +1,49 @@
+//! Checks run by the store before its first operation.
+
+use std::sync::Arc;
+
+use tokio::sync::OnceCell;
+
+/// A check run once, before the first operation of the store, configured with
+/// `PostgresStore::with_startup_check`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StartupCheck {
+    /// No check is run. This is the default.
+    #[default]
+    None,
+    /// Verifies that the database can be reached.
+    Ping,
+    /// Verifies that the session table has the expected columns with
+    /// `PostgresStore::verify_schema`.
+    ///
+    /// **Note**: Only available when the `pg-only` feature is enabled.
+    #[cfg(feature = "pg-only")]
+    VerifySchema,
+    /// Runs `PostgresStore::migrate` if some migrations haven't been applied yet.
+    ///
+    /// **Note**: Only available when the `migration` feature is enabled.
+    #[cfg(feature = "migration")]
+    MigrateIfNeeded,
+    /// Fails if some migrations haven't been applied yet.
+    ///
+    /// **Note**: Only available when the `migration` feature is enabled.
+    #[cfg(feature = "migration")]
+    FailIfMigrationsNeeded,
+}
+
+/// The startup check of a store and its outcome, shared by all clones of the store.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct StartupState {
+    pub(crate) check: StartupCheck,
+    /// Set once the check has run; a failure is kept as its error message.
+    pub(crate) outcome: Arc<OnceCell<Result<(), String>>>,
+}
+
+impl StartupState {
+    pub(crate) fn new(check: StartupCheck) -> Self {
+        Self {
+            check,
+            outcome: Arc::new(OnceCell::new()),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+//! Aggregate statistics of the sessions in a store.
+
+use std::fmt;
+
+use serde::Serialize;
+
+/// Counts and sizes of the sessions in a store, as returned by `PostgresStore::stats`.
+///
+/// All figures are taken in a single query, so they are consistent with each other. Sessions
+/// that are neither active nor expired, such as sessions expiring at the exact time of the
+/// query, count towards `total_sessions` only. The statistics serialize to JSON for capacity
+/// dashboards, and their `Display` implementation prints a one-line summary for logs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct StoreStats {
+    /// Number of rows in the session table.
+    pub total_sessions: u64,
+    /// Number of sessions `load()` would return.
+    pub active_sessions: u64,
+    /// Number of sessions the next `delete_expired()` call would delete.
+    pub expired_sessions: u64,
+    /// Size of the stored session data of all sessions, in bytes.
+    pub total_payload_bytes: u64,
+    /// Mean size of the stored session data, in bytes, or 0 without sessions.
+    pub average_payload_bytes: f64,
+}
+
+impl fmt::Display for StoreStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} sessions ({} active, {} expired), payload: {} bytes, {:.1} bytes on average",
+            self.total_sessions,
+            self.active_sessions,
+            self.expired_sessions,
+            self.total_payload_bytes,
+            self.average_payload_bytes,
+        )
+    }
+}
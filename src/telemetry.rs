@@ -0,0 +1,104 @@
+//! Pluggable observability for store operations.
+//!
+//! A [`TelemetryProvider`] installed with `PostgresStore::with_telemetry_provider` is notified
+//! after every session operation and whenever session data is written or read. This keeps the
+//! store independent from any particular metrics or tracing stack: a provider for `tracing` is
+//! always available, and providers for Prometheus and OpenTelemetry are shipped behind the
+//! `prometheus` and `otel` features.
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::operation::StoreOperation;
+
+#[cfg(feature = "otel")]
+mod otel;
+#[cfg(feature = "prometheus")]
+mod prometheus;
+
+#[cfg(feature = "otel")]
+pub use otel::OtelTelemetryProvider;
+#[cfg(feature = "prometheus")]
+pub use prometheus::PrometheusTelemetryProvider;
+
+/// Receives measurements of the operations performed by the store.
+///
+/// Implementations are called inline on the request path, so they should be cheap and must not
+/// block.
+pub trait TelemetryProvider: Send + Sync + 'static {
+    /// Called once after every store operation completes.
+    ///
+    /// `duration` covers the whole operation, including encoding and decoding, and `success`
+    /// is `false` if the operation returned an error.
+    fn record_operation(&self, op: StoreOperation, duration: Duration, success: bool);
+
+    /// Called with the size of the encoded session data each time a session is written or read.
+    fn record_session_size(&self, bytes: usize);
+
+    /// Called with the session data each time a session is written or read.
+    ///
+    /// `data` is a JSON object mapping session keys to their values, passed through the
+    /// redactor configured with `PostgresStore::with_session_data_redaction`, if any. The data
+    /// written to and read from the database is never redacted. The default implementation
+    /// ignores the data.
+    fn record_session_data(&self, op: StoreOperation, data: &serde_json::Value) {
+        let _ = (op, data);
+    }
+}
+
+/// A [`TelemetryProvider`] that emits a `tracing` event for every measurement.
+///
+/// Operations are logged at `DEBUG` level, or at `WARN` level when they fail. Session sizes and
+/// session data are logged at `TRACE` level. Session data may hold personal information, so
+/// consider configuring a redactor with `PostgresStore::with_session_data_redaction`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoggingTelemetryProvider;
+
+impl TelemetryProvider for LoggingTelemetryProvider {
+    fn record_operation(&self, op: StoreOperation, duration: Duration, success: bool) {
+        let duration_ms = duration.as_secs_f64() * 1000.0;
+
+        if success {
+            tracing::debug!(operation = %op, duration_ms, "session store operation completed");
+        } else {
+            tracing::warn!(operation = %op, duration_ms, "session store operation failed");
+        }
+    }
+
+    fn record_session_size(&self, bytes: usize) {
+        tracing::trace!(bytes, "session data size");
+    }
+
+    fn record_session_data(&self, op: StoreOperation, data: &serde_json::Value) {
+        tracing::trace!(operation = %op, data = %data, "session data");
+    }
+}
+
+/// A shared telemetry provider, cloned along with the store.
+#[derive(Clone)]
+pub(crate) struct Telemetry(Arc<dyn TelemetryProvider>);
+
+impl Telemetry {
+    pub(crate) fn new(provider: Arc<dyn TelemetryProvider>) -> Self {
+        Self(provider)
+    }
+
+    pub(crate) fn record_operation(&self, op: StoreOperation, duration: Duration, success: bool) {
+        self.0.record_operation(op, duration, success);
+    }
+
+    pub(crate) fn record_session_size(&self, bytes: usize) {
+        self.0.record_session_size(bytes);
+    }
+
+    pub(crate) fn record_session_data(&self, op: StoreOperation, data: &serde_json::Value) {
+        self.0.record_session_data(op, data);
+    }
+}
+
+impl fmt::Debug for Telemetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Telemetry").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use opentelemetry::metrics::{Histogram, Meter};
+use opentelemetry::KeyValue;
+
+use super::TelemetryProvider;
+use crate::operation::StoreOperation;
+
+/// A [`TelemetryProvider`] that records OpenTelemetry histograms.
+///
+/// The following instruments are created:
+///
+/// - `tower_sessions_seaorm.operation.duration` - Duration of the store operations in seconds,
+///   with the `operation` and `success` attributes
+/// - `tower_sessions_seaorm.session.size` - Size of the encoded session data in bytes
+///
+/// **Note**: This provider is only available when the `otel` feature is enabled.
+#[derive(Debug, Clone)]
+pub struct OtelTelemetryProvider {
+    operation_duration: Histogram<f64>,
+    session_size: Histogram<u64>,
+}
+
+impl OtelTelemetryProvider {
+    /// Creates the instruments on `meter`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::sync::Arc;
+    /// use tower_sessions_seaorm_store::{OtelTelemetryProvider, PostgresStore};
+    ///
+    /// # fn example(store: PostgresStore) {
+    /// let meter = opentelemetry::global::meter("sessions");
+    /// let store = store.with_telemetry_provider(Arc::new(OtelTelemetryProvider::new(&meter)));
+    /// # }
+    /// ```
+    pub fn new(meter: &Meter) -> Self {
+        Self {
+            operation_duration: meter
+                .f64_histogram("tower_sessions_seaorm.operation.duration")
+                .with_description("Duration of session store operations")
+                .with_unit("s")
+                .build(),
+            session_size: meter
+                .u64_histogram("tower_sessions_seaorm.session.size")
+                .with_description("Size of the encoded session data")
+                .with_unit("By")
+                .build(),
+        }
+    }
+}
+
+impl TelemetryProvider for OtelTelemetryProvider {
+    fn record_operation(&self, op: StoreOperation, duration: Duration, success: bool) {
+        self.operation_duration.record(
+            duration.as_secs_f64(),
+            &[
+                KeyValue::new("operation", op.as_str()),
+                KeyValue::new("success", success),
+            ],
+        );
+    }
+
+    fn record_session_size(&self, bytes: usize) {
+        self.session_size.record(bytes as u64, &[]);
+    }
+}
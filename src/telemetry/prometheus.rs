@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use prometheus::{Histogram, HistogramOpts, HistogramVec, Registry};
+
+use super::TelemetryProvider;
+use crate::operation::StoreOperation;
+
+/// A [`TelemetryProvider`] that records Prometheus histograms.
+///
+/// The following metrics are registered:
+///
+/// - `tower_sessions_seaorm_operation_duration_seconds` - Duration of the store operations,
+///   labelled with `operation` and `outcome` (`success` or `failure`)
+/// - `tower_sessions_seaorm_session_size_bytes` - Size of the encoded session data
+///
+/// **Note**: This provider is only available when the `prometheus` feature is enabled.
+#[derive(Debug, Clone)]
+pub struct PrometheusTelemetryProvider {
+    operation_duration: HistogramVec,
+    session_size: Histogram,
+}
+
+impl PrometheusTelemetryProvider {
+    /// Creates the metrics and registers them with `registry`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if metrics with the same names are already registered.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::sync::Arc;
+    /// use tower_sessions_seaorm_store::{PostgresStore, PrometheusTelemetryProvider};
+    ///
+    /// # fn example(store: PostgresStore) -> prometheus::Result<()> {
+    /// let registry = prometheus::Registry::new();
+    /// let provider = PrometheusTelemetryProvider::new(&registry)?;
+    /// let store = store.with_telemetry_provider(Arc::new(provider));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(registry: &Registry) -> prometheus::Result<Self> {
+        let operation_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "tower_sessions_seaorm_operation_duration_seconds",
+                "Duration of session store operations",
+            ),
+            &["operation", "outcome"],
+        )?;
+        let session_size = Histogram::with_opts(
+            HistogramOpts::new(
+                "tower_sessions_seaorm_session_size_bytes",
+                "Size of the encoded session data",
+            )
+            .buckets(prometheus::exponential_buckets(64.0, 4.0, 8)?),
+        )?;
+
+        registry.register(Box::new(operation_duration.clone()))?;
+        registry.register(Box::new(session_size.clone()))?;
+
+        Ok(Self {
+            operation_duration,
+            session_size,
+        })
+    }
+}
+
+impl TelemetryProvider for PrometheusTelemetryProvider {
+    fn record_operation(&self, op: StoreOperation, duration: Duration, success: bool) {
+        let outcome = if success { "success" } else { "failure" };
+        self.operation_duration
+            .with_label_values(&[op.as_str(), outcome])
+            .observe(duration.as_secs_f64());
+    }
+
+    fn record_session_size(&self, bytes: usize) {
+        self.session_size.observe(bytes as f64);
+    }
+}
@@ -0,0 +1,38 @@
+//! Per-operation time limits.
+
+use std::time::Duration;
+
+use crate::operation::StoreOperation;
+
+/// Time limits for the individual store operations, used with
+/// `PostgresStore::with_per_operation_timeout`.
+///
+/// An operation that doesn't complete within its limit fails with
+/// `session_store::Error::Backend("operation timeout")`. `None` means no limit, which is the
+/// default for every operation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OperationTimeouts {
+    /// Limit for `SessionStore::create`.
+    pub create: Option<Duration>,
+    /// Limit for `SessionStore::save` and `PostgresStore::conditional_save`.
+    pub save: Option<Duration>,
+    /// Limit for `SessionStore::load`.
+    pub load: Option<Duration>,
+    /// Limit for `SessionStore::delete`.
+    pub delete: Option<Duration>,
+    /// Limit for `ExpiredDeletion::delete_expired` and `PostgresStore::delete_expired_with_lock`.
+    pub delete_expired: Option<Duration>,
+}
+
+impl OperationTimeouts {
+    /// Returns the limit for `op`, if any.
+    pub(crate) fn get(&self, op: StoreOperation) -> Option<Duration> {
+        match op {
+            StoreOperation::Create => self.create,
+            StoreOperation::Save => self.save,
+            StoreOperation::Load => self.load,
+            StoreOperation::Delete => self.delete,
+            StoreOperation::DeleteExpired => self.delete_expired,
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Backend-specific ways of writing a session row that may already exist.
+
+use sea_orm::sea_query::{Alias, DynIden, Expr, Func, InsertStatement, OnConflict, SimpleExpr};
+use sea_orm::DbBackend;
+
+use crate::entity::session;
+
+/// How a single statement inserts a session row or handles an existing one.
+///
+/// The strategy is selected at runtime from the backend of the connection, so one store type
+/// works over every Sea-ORM backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UpsertStrategy {
+    /// `INSERT ... ON CONFLICT (id) DO ...`, as supported by PostgreSQL and SQLite.
+    OnConflict,
+    /// `INSERT ... ON DUPLICATE KEY UPDATE ...`, as supported by MySQL.
+    OnDuplicateKey,
+    /// `INSERT ... ON CONFLICT (id, expiry_date) DO ...`, for PostgreSQL tables partitioned by
+    /// expiry date, whose primary key includes it.
+    PartitionedOnConflict,
+}
+
+impl UpsertStrategy {
+    /// Returns the strategy supported by `backend`.
+    pub(crate) fn for_backend(backend: DbBackend) -> Self {
+        match backend {
+            DbBackend::Postgres | DbBackend::Sqlite => UpsertStrategy::OnConflict,
+            DbBackend::MySql => UpsertStrategy::OnDuplicateKey,
+        }
+    }
+
+    /// Makes `insert` overwrite an existing row with the same ID using `values`, if the row
+    /// matches `condition`.
+    ///
+    /// MySQL can't make the overwrite conditional, so there each assignment keeps the value of
+    /// the existing row unless it matches `condition`. MySQL evaluates the assignments in order,
+    /// so `condition` must not read columns the overwrite changes.
+    pub(crate) fn overwrite(
+        self,
+        insert: &mut InsertStatement,
+        values: Vec<(DynIden, SimpleExpr)>,
+        condition: Option<SimpleExpr>,
+    ) {
+        let mut on_conflict = match self {
+            UpsertStrategy::OnConflict => OnConflict::column(session::Column::Id).values(values).to_owned(),
+            UpsertStrategy::PartitionedOnConflict => OnConflict::columns([session::Column::Id, session::Column::ExpiryDate])
+                .values(values)
+                .to_owned(),
+            // MySQL has no conflict target, any duplicate key triggers the update
+            UpsertStrategy::OnDuplicateKey => {
+                let values = match condition {
+                    Some(condition) => values
+                        .into_iter()
+                        .map(|(column, value)| {
+                            let kept = Expr::col(column.clone());
+                            let value = Func::cust(Alias::new("IF")).args([condition.clone(), value, kept.into()]);
+                            (column, value.into())
+                        })
+                        .collect(),
+                    None => values,
+                };
+                insert.on_conflict(OnConflict::new().values(values).to_owned());
+                return;
+            }
+        };
+        on_conflict.action_and_where_option(condition);
+        insert.on_conflict(on_conflict);
+    }
+
+    /// Makes `insert` leave an existing row with the same ID untouched.
+    pub(crate) fn ignore(self, insert: &mut InsertStatement) {
+        let on_conflict = match self {
+            UpsertStrategy::OnConflict => OnConflict::column(session::Column::Id).do_nothing().to_owned(),
+            UpsertStrategy::PartitionedOnConflict => OnConflict::columns([session::Column::Id, session::Column::ExpiryDate])
+                .do_nothing()
+                .to_owned(),
+            // MySQL has no DO NOTHING, so the ID is assigned to itself instead
+            UpsertStrategy::OnDuplicateKey => OnConflict::new().do_nothing_on([session::Column::Id]).to_owned(),
+        };
+        insert.on_conflict(on_conflict);
+    }
+}
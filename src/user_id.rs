@@ -0,0 +1,91 @@
+//! Extraction of the user owning a session from its data, and the sessions of a user.
+
+use std::fmt;
+use std::sync::Arc;
+
+use serde_json::Value;
+use time::OffsetDateTime;
+use tower_sessions::session::{Id, Record};
+
+/// A function returning the ID of the user owning a session, if any.
+pub type UserIdExtractor = Arc<dyn Fn(&Record) -> Option<String> + Send + Sync>;
+
+/// Returns an extractor reading the user ID from the session key `key`.
+///
+/// String values are used as they are and numbers are converted to their decimal form. Sessions
+/// without the key, or with a value of any other type, have no user ID.
+///
+/// # Examples
+///
+/// ```
+/// use tower_sessions::session::{Id, Record};
+/// use tower_sessions_seaorm_store::user_id_key;
+///
+/// let mut record = Record {
+///     id: Id::default(),
+///     data: Default::default(),
+///     expiry_date: time::OffsetDateTime::now_utc(),
+/// };
+/// record.data.insert("user_id".to_string(), 42.into());
+///
+/// assert_eq!(user_id_key("user_id")(&record), Some("42".to_string()));
+/// ```
+pub fn user_id_key(key: impl Into<String>) -> UserIdExtractor {
+    let key = key.into();
+    Arc::new(move |record: &Record| match record.data.get(&key)? {
+        Value::String(user_id) => Some(user_id.clone()),
+        Value::Number(user_id) => Some(user_id.to_string()),
+        _ => None,
+    })
+}
+
+/// An active session of a user, as listed by `PostgresStore::sessions_for_user`.
+///
+/// Holds the metadata of the session rather than its data, e.g. for a screen listing the
+/// devices a user is logged in on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserSession {
+    /// The ID of the session.
+    pub id: Id,
+    /// When the session expires.
+    pub expiry_date: OffsetDateTime,
+    /// When the session was last written.
+    pub last_accessed_at: OffsetDateTime,
+    /// When the session was created, or `None` if it was created before the store recorded
+    /// creation times.
+    pub created_at: Option<OffsetDateTime>,
+    /// The IP address of the client that last wrote the session, if known.
+    ///
+    /// **Note**: Only available when the `client-metadata` feature is enabled.
+    #[cfg(feature = "client-metadata")]
+    pub ip_address: Option<std::net::IpAddr>,
+    /// The user agent of the client that last wrote the session, if known.
+    ///
+    /// **Note**: Only available when the `client-metadata` feature is enabled.
+    #[cfg(feature = "client-metadata")]
+    pub user_agent: Option<String>,
+}
+
+/// The user ID extractor of a store, if any, cloned along with the store.
+#[derive(Clone, Default)]
+pub(crate) struct UserIdExtractorHandle(Option<UserIdExtractor>);
+
+impl UserIdExtractorHandle {
+    pub(crate) fn new(extractor: UserIdExtractor) -> Self {
+        Self(Some(extractor))
+    }
+
+    /// Returns the user ID of `record`, or `None` if no extractor is configured.
+    pub(crate) fn extract(&self, record: &Record) -> Option<String> {
+        self.0.as_ref().and_then(|extractor| extractor(record))
+    }
+}
+
+impl fmt::Debug for UserIdExtractorHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(_) => f.debug_tuple("UserIdExtractor").finish_non_exhaustive(),
+            None => f.write_str("None"),
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Validators for user-supplied identifiers.
+
+use std::fmt;
+use std::sync::Arc;
+
+use tower_sessions::session::Id;
+
+/// A function deciding whether a table or schema name is acceptable.
+pub type TableValidator = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// Accepts non-empty names made only of ASCII letters, digits and underscores.
+///
+/// This is the validator used by `PostgresStore` unless another one is configured with
+/// `PostgresStore::with_table_validator`. It rejects quotes, semicolons, whitespace, dots and
+/// any other character that could change the meaning of a statement.
+///
+/// # Examples
+///
+/// ```
+/// use tower_sessions_seaorm_store::default_table_validator;
+///
+/// assert!(default_table_validator("tenant_a_sessions"));
+/// assert!(!default_table_validator("sessions\"; DROP TABLE users; --"));
+/// ```
+pub fn default_table_validator(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || character == '_')
+}
+
+/// The table validator of a store, cloned along with the store.
+#[derive(Clone)]
+pub(crate) struct TableValidatorHandle(TableValidator);
+
+impl TableValidatorHandle {
+    pub(crate) fn new(validator: TableValidator) -> Self {
+        Self(validator)
+    }
+
+    /// Returns `name` if the validator accepts it.
+    pub(crate) fn validate(&self, name: String) -> Result<String, crate::SeaOrmStoreError> {
+        if (self.0)(&name) {
+            Ok(name)
+        } else {
+            Err(crate::SeaOrmStoreError::InvalidIdentifier(name))
+        }
+    }
+}
+
+impl Default for TableValidatorHandle {
+    fn default() -> Self {
+        Self::new(Arc::new(default_table_validator))
+    }
+}
+
+impl fmt::Debug for TableValidatorHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TableValidator").finish_non_exhaustive()
+    }
+}
+
+/// A function deciding whether a session ID is acceptable.
+pub type SessionIdValidator = Arc<dyn Fn(&Id) -> bool + Send + Sync>;
+
+/// Rejects the all-zero session ID.
+///
+/// `tower-sessions` generates IDs randomly, so an all-zero ID points to a forged cookie or an
+/// uninitialized value rather than a real session. Install it with
+/// `PostgresStore::with_session_id_validator`.
+///
+/// # Examples
+///
+/// ```
+/// use tower_sessions_seaorm_store::{non_zero_id_validator, Id};
+///
+/// assert!(non_zero_id_validator(&Id::default()));
+/// assert!(!non_zero_id_validator(&Id(0)));
+/// ```
+pub fn non_zero_id_validator(id: &Id) -> bool {
+    id.0 != 0
+}
+
+/// The session ID validator of a store, if any, cloned along with the store.
+#[derive(Clone, Default)]
+pub(crate) struct SessionIdValidatorHandle(Option<SessionIdValidator>);
+
+impl SessionIdValidatorHandle {
+    pub(crate) fn new(validator: SessionIdValidator) -> Self {
+        Self(Some(validator))
+    }
+
+    /// Succeeds if no validator is configured or if it accepts `id`.
+    pub(crate) fn validate(&self, id: &Id) -> Result<(), crate::SeaOrmStoreError> {
+        match &self.0 {
+            Some(validator) if !validator(id) => Err(crate::SeaOrmStoreError::InvalidSessionId),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Debug for SessionIdValidatorHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(_) => f.debug_tuple("SessionIdValidator").finish_non_exhaustive(),
+            None => f.write_str("None"),
+        }
+    }
+}
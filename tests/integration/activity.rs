@@ -0,0 +1,105 @@
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::mpsc;
+use tower_sessions::session::Id;
+use tower_sessions::{ExpiredDeletion, SessionStore};
+use tower_sessions_seaorm_store::{ActivityEvent, ChannelActivityTracker, SessionActivityTracker};
+
+use crate::helpers::{active_record, expired_record, TestDb};
+
+/// Records every event it receives.
+#[derive(Default)]
+struct MockActivityTracker {
+    events: Mutex<Vec<ActivityEvent>>,
+}
+
+impl MockActivityTracker {
+    fn take_events(&self) -> Vec<ActivityEvent> {
+        std::mem::take(&mut *self.events.lock().unwrap())
+    }
+}
+
+impl SessionActivityTracker for MockActivityTracker {
+    fn on_session_loaded(&self, id: &Id, found: bool) {
+        self.events.lock().unwrap().push(ActivityEvent::Loaded { id: *id, found });
+    }
+
+    fn on_session_created(&self, id: &Id) {
+        self.events.lock().unwrap().push(ActivityEvent::Created { id: *id });
+    }
+
+    fn on_session_deleted(&self, id: &Id) {
+        self.events.lock().unwrap().push(ActivityEvent::Deleted { id: *id });
+    }
+
+    fn on_session_expired(&self, count: u64) {
+        self.events.lock().unwrap().push(ActivityEvent::Expired { count });
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn every_hook_is_called_after_its_operation() {
+    let db = TestDb::migrated().await;
+    let tracker = Arc::new(MockActivityTracker::default());
+    let store = db.store().with_session_activity_tracker(tracker.clone());
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    assert_eq!(tracker.take_events(), [ActivityEvent::Created { id: record.id }]);
+
+    store.load(&record.id).await.unwrap();
+    assert_eq!(tracker.take_events(), [ActivityEvent::Loaded { id: record.id, found: true }]);
+
+    // Saving isn't tracked
+    store.save(&record).await.unwrap();
+    assert_eq!(tracker.take_events(), []);
+
+    store.delete(&record.id).await.unwrap();
+    assert_eq!(tracker.take_events(), [ActivityEvent::Deleted { id: record.id }]);
+
+    store.load(&record.id).await.unwrap();
+    assert_eq!(tracker.take_events(), [ActivityEvent::Loaded { id: record.id, found: false }]);
+
+    for _ in 0..2 {
+        store.create(&mut expired_record()).await.unwrap();
+    }
+    tracker.take_events();
+    store.delete_expired().await.unwrap();
+    assert_eq!(tracker.take_events(), [ActivityEvent::Expired { count: 2 }]);
+
+    store.delete_expired_with_lock().await.unwrap();
+    assert_eq!(tracker.take_events(), [ActivityEvent::Expired { count: 0 }]);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn failed_operations_are_not_tracked() {
+    let db = TestDb::new().await;
+    // Without migrations the session table doesn't exist
+    let tracker = Arc::new(MockActivityTracker::default());
+    let store = db.store().with_session_activity_tracker(tracker.clone());
+
+    assert!(store.create(&mut active_record()).await.is_err());
+    assert!(store.load(&Id::default()).await.is_err());
+    assert!(store.delete(&Id::default()).await.is_err());
+    assert!(store.delete_expired().await.is_err());
+
+    assert_eq!(tracker.take_events(), []);
+}
+
+#[tokio::test]
+async fn channel_tracker_forwards_events_and_drops_them_when_full() {
+    let (sender, mut receiver) = mpsc::channel(2);
+    let tracker = ChannelActivityTracker(sender);
+    let id = Id::default();
+
+    tracker.on_session_created(&id);
+    tracker.on_session_expired(3);
+    // The channel is full, so this event is dropped instead of blocking
+    tracker.on_session_deleted(&id);
+
+    assert_eq!(receiver.recv().await, Some(ActivityEvent::Created { id }));
+    assert_eq!(receiver.recv().await, Some(ActivityEvent::Expired { count: 3 }));
+    assert!(receiver.try_recv().is_err());
+}
@@ -0,0 +1,47 @@
+use sea_orm::{ConnectionTrait, DbBackend, Statement, TransactionTrait};
+use tower_sessions::SessionStore;
+
+use crate::helpers::{active_record, expired_record, TestDb};
+
+/// The advisory lock key of `"tower_sessions"."session"`: the 64-bit FNV-1a hash of
+/// `tower_sessions.session`.
+fn lock_key() -> i64 {
+    let hash = b"tower_sessions.session"
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+    hash as i64
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_expired_with_lock_skips_while_another_instance_holds_the_lock() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    store.create(&mut active_record()).await.unwrap();
+    store.create(&mut expired_record()).await.unwrap();
+
+    // Another instance cleaning up
+    let lock = db.conn.begin().await.unwrap();
+    let acquired = lock
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT pg_try_advisory_xact_lock($1) AS acquired",
+            [lock_key().into()],
+        ))
+        .await
+        .unwrap()
+        .unwrap()
+        .try_get::<bool>("", "acquired")
+        .unwrap();
+    assert!(acquired);
+
+    store.delete_expired_with_lock().await.unwrap();
+    assert_eq!(db.count_rows("session").await, 2);
+
+    // Committing releases the lock
+    lock.commit().await.unwrap();
+    store.delete_expired_with_lock().await.unwrap();
+    assert_eq!(db.count_rows("session").await, 1);
+}
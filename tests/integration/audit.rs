@@ -0,0 +1,239 @@
+use std::time::Duration;
+
+use sea_orm::{ConnectionTrait, DbBackend, FromQueryResult, Statement};
+use tower_sessions::session::Id;
+use tower_sessions::{session_store, ExpiredDeletion, SessionStore};
+use tower_sessions_seaorm_store::CircuitBreakerConfig;
+
+use crate::helpers::{active_record, expired_record, TestDb};
+
+#[derive(Debug, PartialEq, FromQueryResult)]
+struct AuditEntry {
+    session_id: String,
+    operation: String,
+    actor: Option<String>,
+    data_hash: Option<String>,
+}
+
+async fn audit_entries(db: &TestDb) -> Vec<AuditEntry> {
+    AuditEntry::find_by_statement(Statement::from_string(
+        DbBackend::Postgres,
+        "SELECT session_id, operation, actor, data_hash FROM tower_sessions.session_audit_log ORDER BY id",
+    ))
+    .all(&db.conn)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_is_audited() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_actor("admin".to_string());
+    let record = active_record();
+
+    store.delete(&record.id).await.unwrap();
+
+    assert_eq!(
+        audit_entries(&db).await,
+        [AuditEntry {
+            session_id: record.id.to_string(),
+            operation: "delete".to_string(),
+            actor: Some("admin".to_string()),
+            data_hash: None,
+        }]
+    );
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn failed_operations_keep_their_error_when_the_audit_log_fails() {
+    let db = TestDb::migrated().await;
+    // Neither the session table nor the audit log exist in this schema
+    let store = db
+        .store()
+        .with_schema_name("missing")
+        .unwrap()
+        .with_circuit_breaker(CircuitBreakerConfig {
+            failure_threshold: 1,
+            success_threshold: 1,
+            probe_interval: Duration::from_secs(60),
+        });
+
+    let result = store.load(&active_record().id).await;
+
+    // The error of the query, not that of the audit log entry refused by the circuit it opened
+    assert!(
+        matches!(&result, Err(session_store::Error::Backend(message)) if message != "circuit open"),
+        "{result:?}"
+    );
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn every_operation_is_audited_with_the_data_hash() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut record = active_record();
+    // Enough keys for two copies of the data to iterate in different orders
+    for key in ["a", "b", "c", "d", "e", "f"] {
+        record.data.insert(key.to_string(), key.into());
+    }
+
+    store.create(&mut record).await.unwrap();
+    store.load(&record.id).await.unwrap();
+    record.data.insert("theme".to_string(), "light".into());
+    store.save(&record).await.unwrap();
+
+    let entries = audit_entries(&db).await;
+    let operations: Vec<_> = entries.iter().map(|entry| entry.operation.as_str()).collect();
+    assert_eq!(operations, ["create", "load", "save"]);
+    assert!(entries.iter().all(|entry| entry.session_id == record.id.to_string() && entry.actor.is_none()));
+
+    // The loaded data is the created data, and saving changed it
+    let hashes: Vec<_> = entries.iter().map(|entry| entry.data_hash.clone().unwrap()).collect();
+    assert_eq!(hashes[0].len(), 64);
+    assert_eq!(hashes[0], hashes[1]);
+    assert_ne!(hashes[1], hashes[2]);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn copies_are_audited_as_created() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    let copy_id = Id::default();
+    store.copy_session(&record.id, &copy_id).await.unwrap();
+
+    let entries = audit_entries(&db).await;
+    let copy = entries.last().unwrap();
+    assert_eq!((copy.session_id.as_str(), copy.operation.as_str()), (copy_id.to_string().as_str(), "create"));
+    assert!(copy.data_hash.is_some());
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_many_audits_the_deleted_sessions() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    store.delete_many(&[Id::default(), record.id]).await.unwrap();
+
+    let entries = audit_entries(&db).await;
+    let deletions: Vec<_> = entries.iter().filter(|entry| entry.operation == "delete").collect();
+    assert_eq!(deletions.len(), 1);
+    assert_eq!(deletions[0].session_id, record.id.to_string());
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn failed_operation_is_audited() {
+    let db = TestDb::migrated().await;
+    // The audit log lives next to the missing session table
+    let store = db.store().with_table_name("missing_sessions").unwrap();
+    let record = active_record();
+
+    assert!(store.load(&record.id).await.is_err());
+
+    let entries = audit_entries(&db).await;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].operation, "load");
+    assert_eq!(entries[0].data_hash, None);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn expired_sessions_are_audited_when_deleted() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_actor("cleanup".to_string());
+    let mut active = active_record();
+    let mut expired = expired_record();
+    db.store().create(&mut active).await.unwrap();
+    db.store().create(&mut expired).await.unwrap();
+
+    store.delete_expired().await.unwrap();
+
+    let entries = audit_entries(&db).await;
+    assert_eq!(
+        entries.last().unwrap(),
+        &AuditEntry {
+            session_id: expired.id.to_string(),
+            operation: "delete_expired".to_string(),
+            actor: Some("cleanup".to_string()),
+            data_hash: None,
+        }
+    );
+    assert_eq!(entries.iter().filter(|entry| entry.operation == "delete_expired").count(), 1);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn batched_deletion_audits_every_deleted_session() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut expired = Vec::new();
+    for _ in 0..5 {
+        let mut record = expired_record();
+        store.create(&mut record).await.unwrap();
+        expired.push(record.id.to_string());
+    }
+
+    assert_eq!(store.delete_expired_batched(2).await.unwrap(), 5);
+
+    let mut audited: Vec<_> = audit_entries(&db)
+        .await
+        .into_iter()
+        .filter(|entry| entry.operation == "delete_expired")
+        .map(|entry| entry.session_id)
+        .collect();
+    audited.sort();
+    expired.sort();
+    assert_eq!(audited, expired);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn audit_log_is_append_only() {
+    let db = TestDb::migrated().await;
+    let mut record = active_record();
+    db.store().create(&mut record).await.unwrap();
+
+    for sql in [
+        "UPDATE tower_sessions.session_audit_log SET actor = 'someone else'",
+        "DELETE FROM tower_sessions.session_audit_log",
+        "TRUNCATE tower_sessions.session_audit_log",
+    ] {
+        let result = db.conn.execute_unprepared(sql).await;
+        assert!(
+            result.as_ref().is_err_and(|err| err.to_string().contains("append-only")),
+            "{sql}: {result:?}"
+        );
+    }
+    assert_eq!(audit_entries(&db).await.len(), 1);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn purged_sessions_are_audited_as_deleted() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_actor("privacy-team".to_string());
+    let mut record = active_record();
+    db.store().create(&mut record).await.unwrap();
+
+    assert_eq!(store.purge_where(sea_orm::Condition::all()).await.unwrap(), 1);
+
+    assert_eq!(
+        audit_entries(&db).await.last().unwrap(),
+        &AuditEntry {
+            session_id: record.id.to_string(),
+            operation: "delete".to_string(),
+            actor: Some("privacy-team".to_string()),
+            data_hash: None,
+        }
+    );
+}
@@ -0,0 +1,305 @@
+use sea_orm::Database;
+use time::{Duration, OffsetDateTime, UtcOffset};
+use tower_sessions::session::{Id, Record};
+use tower_sessions::{ExpiredDeletion, SessionStore};
+use tower_sessions_seaorm_store::{user_id_key, ConditionalSaveExpr, EvictionPolicy, SeaOrmStore};
+
+use crate::helpers::{active_record, assert_loads, assert_missing, expired_record, record};
+
+/// Returns a store over a migrated in-memory SQLite database.
+async fn sqlite_store() -> SeaOrmStore {
+    let conn = Database::connect("sqlite::memory:").await.unwrap();
+    let store = SeaOrmStore::new(conn);
+    store.migrate().await.unwrap();
+    store
+}
+
+#[tokio::test]
+async fn store_works_over_sqlite() {
+    let store = sqlite_store().await;
+    let mut record = active_record();
+
+    store.create(&mut record).await.unwrap();
+    assert_loads(&store, &record).await;
+
+    record.data.insert("theme".to_string(), "light".into());
+    store.save(&record).await.unwrap();
+    assert_loads(&store, &record).await;
+
+    store.delete(&record.id).await.unwrap();
+    assert_missing(&store, &record.id).await;
+}
+
+#[tokio::test]
+async fn sqlite_upserts_overwrite_or_keep_existing_rows() {
+    let store = sqlite_store().await;
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    let mut changed = record.clone();
+    changed.data.insert("theme".to_string(), "light".into());
+    assert!(!store
+        .conditional_save(&changed, ConditionalSaveExpr::OnlyIfNotExists)
+        .await
+        .unwrap());
+    assert_loads(&store, &record).await;
+
+    store.save(&changed).await.unwrap();
+    assert_loads(&store, &changed).await;
+    assert_eq!(store.session_version(&record.id).await.unwrap(), Some(1));
+}
+
+#[tokio::test]
+async fn sqlite_expired_sessions_are_deleted() {
+    let store = sqlite_store().await;
+    let mut active = active_record();
+    let mut expired = expired_record();
+    store.create(&mut active).await.unwrap();
+    store.create(&mut expired).await.unwrap();
+
+    assert_missing(&store, &expired.id).await;
+    store.delete_expired().await.unwrap();
+    assert_eq!(store.count_active().await.unwrap(), 1);
+    assert_loads(&store, &active).await;
+}
+
+#[tokio::test]
+async fn sqlite_expired_sessions_are_deleted_in_batches() {
+    let store = sqlite_store().await;
+    let mut active = active_record();
+    store.create(&mut active).await.unwrap();
+    for _ in 0..5 {
+        store.create(&mut expired_record()).await.unwrap();
+    }
+
+    assert_eq!(store.delete_expired_batched(2).await.unwrap(), 5);
+    assert_eq!(store.count_active().await.unwrap(), 1);
+    assert_loads(&store, &active).await;
+}
+
+#[tokio::test]
+async fn sqlite_lists_sessions_of_a_user() {
+    let conn = Database::connect("sqlite::memory:").await.unwrap();
+    let store = SeaOrmStore::new(conn).with_user_id_extractor(user_id_key("user_id"));
+    store.migrate().await.unwrap();
+
+    // The record holds `"user_id": 42`
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    let sessions = store.sessions_for_user("42", 0, 10).await.unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].id, record.id);
+    assert_eq!(sessions[0].expiry_date.unix_timestamp(), record.expiry_date.unix_timestamp());
+}
+
+#[tokio::test]
+async fn sqlite_migrations_can_run_again() {
+    let store = sqlite_store().await;
+    store.migrate().await.unwrap();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    assert_loads(&store, &record).await;
+}
+
+#[tokio::test]
+async fn sqlite_migrations_can_be_rolled_back() {
+    let store = sqlite_store().await;
+    store.migrate_down(None).await.unwrap();
+    assert!(store.load(&Id::default()).await.is_err());
+
+    store.migrate().await.unwrap();
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    assert_loads(&store, &record).await;
+}
+
+#[tokio::test]
+async fn sqlite_store_works_over_generated_sql() {
+    use sea_orm::{ConnectionTrait, DbBackend};
+    use tower_sessions_seaorm_store::migration::generate_sql;
+
+    let conn = Database::connect("sqlite::memory:").await.unwrap();
+    conn.execute_unprepared(&generate_sql(DbBackend::Sqlite, "session", "ignored"))
+        .await
+        .unwrap();
+    let store = SeaOrmStore::new(conn);
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    assert_loads(&store, &record).await;
+}
+
+#[tokio::test]
+async fn sqlite_compares_expiry_dates_across_utc_offsets() {
+    let store = sqlite_store().await;
+    let offset = UtcOffset::from_hms(5, 0, 0).unwrap();
+    let now = OffsetDateTime::now_utc();
+
+    // Written as +05:00 text, the expired date would sort after the current UTC time
+    let mut active = record((now + Duration::hours(1)).to_offset(offset));
+    let mut expired = record((now - Duration::hours(1)).to_offset(offset));
+    store.create(&mut active).await.unwrap();
+    store.create(&mut expired).await.unwrap();
+
+    assert_missing(&store, &expired.id).await;
+    store.delete_expired().await.unwrap();
+    assert_eq!(store.count_active().await.unwrap(), 1);
+
+    let loaded = store.load(&active.id).await.unwrap().unwrap();
+    assert_eq!(loaded.expiry_date, active.expiry_date);
+}
+
+#[tokio::test]
+async fn sqlite_stats_count_sessions_and_payload_bytes() {
+    let store = sqlite_store().await;
+    store.create(&mut active_record()).await.unwrap();
+    store.create(&mut expired_record()).await.unwrap();
+
+    let stats = store.stats().await.unwrap();
+
+    assert_eq!((stats.total_sessions, stats.active_sessions, stats.expired_sessions), (2, 1, 1));
+    assert!(stats.total_payload_bytes > 0);
+    assert_eq!(stats.average_payload_bytes, stats.total_payload_bytes as f64 / 2.0);
+}
+
+#[tokio::test]
+async fn sqlite_soft_deletes_sessions() {
+    let store = sqlite_store().await.with_soft_delete(Duration::days(30));
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    store.delete(&record.id).await.unwrap();
+
+    assert_missing(&store, &record.id).await;
+    assert_eq!(store.purge_deleted().await.unwrap(), 0);
+    assert_eq!(store.clone().with_soft_delete(Duration::ZERO).purge_deleted().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn sqlite_evicts_sessions_beyond_the_limit_per_user() {
+    let conn = Database::connect("sqlite::memory:").await.unwrap();
+    let store = SeaOrmStore::new(conn)
+        .with_user_id_extractor(user_id_key("user_id"))
+        .with_max_sessions_per_user(1, EvictionPolicy::OldestFirst);
+    store.migrate().await.unwrap();
+
+    // Both records hold `"user_id": 42`
+    let mut first = active_record();
+    let mut second = active_record();
+    store.create(&mut first).await.unwrap();
+    store.create(&mut second).await.unwrap();
+
+    assert_missing(&store, &first.id).await;
+    assert_loads(&store, &second).await;
+}
+
+#[tokio::test]
+async fn sqlite_saves_cannot_extend_sessions_past_their_max_lifetime() {
+    use sea_orm::ConnectionTrait;
+
+    let conn = Database::connect("sqlite::memory:").await.unwrap();
+    let store = SeaOrmStore::new(conn.clone()).with_max_lifetime(Duration::days(1));
+    store.migrate().await.unwrap();
+    let mut record = record(OffsetDateTime::now_utc() + Duration::hours(1));
+    store.create(&mut record).await.unwrap();
+    // Created 23 hours ago
+    conn.execute_unprepared("UPDATE session SET created_at = strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now', '-23 hours')")
+    .await
+    .unwrap();
+
+    record.expiry_date = OffsetDateTime::now_utc() + Duration::days(7);
+    store.save(&record).await.unwrap();
+
+    let expiry_date = store.load(&record.id).await.unwrap().unwrap().expiry_date;
+    let deadline = OffsetDateTime::now_utc() + Duration::hours(1);
+    assert!((expiry_date - deadline).abs() < Duration::minutes(1), "{expiry_date}");
+}
+
+#[tokio::test]
+async fn sqlite_load_many_selects_sessions_in_one_query() {
+    let conn = Database::connect("sqlite::memory:").await.unwrap();
+    let store = SeaOrmStore::new(conn);
+    store.migrate().await.unwrap();
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    let records = store.load_many(&[Id::default(), record.id]).await.unwrap();
+
+    assert_eq!(records, [None, Some(record)]);
+}
+
+#[tokio::test]
+async fn sqlite_delete_many_removes_sessions_in_one_statement() {
+    let conn = Database::connect("sqlite::memory:").await.unwrap();
+    let store = SeaOrmStore::new(conn);
+    store.migrate().await.unwrap();
+    let mut record = active_record();
+    let mut kept = active_record();
+    store.create(&mut record).await.unwrap();
+    store.create(&mut kept).await.unwrap();
+
+    assert_eq!(store.delete_many(&[record.id, Id::default()]).await.unwrap(), 1);
+
+    assert_missing(&store, &record.id).await;
+    assert_loads(&store, &kept).await;
+}
+
+#[tokio::test]
+async fn sqlite_touch_moves_the_expiry_date() {
+    let conn = Database::connect("sqlite::memory:").await.unwrap();
+    let store = SeaOrmStore::new(conn);
+    store.migrate().await.unwrap();
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    let expiry_date = OffsetDateTime::now_utc() + Duration::days(7);
+    assert!(store.touch(&record.id, expiry_date).await.unwrap());
+
+    assert_loads(&store, &Record { expiry_date, ..record }).await;
+}
+
+#[tokio::test]
+async fn sqlite_skips_saves_that_dont_change_the_data() {
+    let conn = Database::connect("sqlite::memory:").await.unwrap();
+    let store = SeaOrmStore::new(conn).with_skip_unchanged_saves(Duration::minutes(1));
+    store.migrate().await.unwrap();
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    store.save(&record).await.unwrap();
+    assert_eq!(store.session_version(&record.id).await.unwrap(), Some(0));
+
+    record.data.insert("theme".to_string(), "light".into());
+    store.save(&record).await.unwrap();
+    assert_eq!(store.session_version(&record.id).await.unwrap(), Some(1));
+}
+
+#[cfg(feature = "audit-log")]
+#[tokio::test]
+async fn sqlite_audit_log_is_append_only() {
+    use sea_orm::ConnectionTrait;
+
+    let conn = Database::connect("sqlite::memory:").await.unwrap();
+    let store = SeaOrmStore::new(conn.clone());
+    store.migrate().await.unwrap();
+    store.create(&mut expired_record()).await.unwrap();
+
+    // Cleanup records the expired session next to its creation
+    store.delete_expired().await.unwrap();
+    let count = conn
+        .query_one(sea_orm::Statement::from_string(
+            sea_orm::DbBackend::Sqlite,
+            "SELECT COUNT(*) AS count FROM session_audit_log",
+        ))
+        .await
+        .unwrap()
+        .unwrap()
+        .try_get::<i64>("", "count")
+        .unwrap();
+    assert_eq!(count, 2);
+
+    let result = conn.execute_unprepared("DELETE FROM session_audit_log").await;
+    assert!(result.is_err_and(|err| err.to_string().contains("append-only")));
+}
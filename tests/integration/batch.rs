@@ -0,0 +1,100 @@
+use time::Duration;
+use tower_sessions::session::Id;
+use tower_sessions::SessionStore;
+
+use crate::helpers::{active_record, assert_loads, assert_missing, expired_record, TestDb};
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn load_many_returns_the_records_in_the_order_of_the_ids() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut first = active_record();
+    let mut second = active_record();
+    let mut expired = expired_record();
+    for record in [&mut first, &mut second, &mut expired] {
+        store.create(record).await.unwrap();
+    }
+
+    let records = store
+        .load_many(&[second.id, expired.id, first.id, Id::default(), second.id])
+        .await
+        .unwrap();
+
+    assert_eq!(records, [Some(second.clone()), None, Some(first), None, Some(second)]);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn load_many_of_no_ids_returns_nothing() {
+    let db = TestDb::migrated().await;
+
+    assert!(db.store().load_many(&[]).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_many_removes_the_sessions_and_counts_them() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut first = active_record();
+    let mut second = active_record();
+    let mut kept = active_record();
+    for record in [&mut first, &mut second, &mut kept] {
+        store.create(record).await.unwrap();
+    }
+
+    // Sessions passed twice are deleted and counted once
+    let deleted = store.delete_many(&[first.id, second.id, Id::default(), first.id]).await.unwrap();
+
+    assert_eq!(deleted, 2);
+    assert_missing(&store, &first.id).await;
+    assert_missing(&store, &second.id).await;
+    assert_loads(&store, &kept).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_many_marks_sessions_in_soft_delete_mode() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_soft_delete(Duration::days(30));
+    let mut first = active_record();
+    let mut second = active_record();
+    store.create(&mut first).await.unwrap();
+    store.create(&mut second).await.unwrap();
+
+    assert_eq!(store.delete_many(&[first.id, second.id]).await.unwrap(), 2);
+    // Sessions deleted already aren't counted again
+    assert_eq!(store.delete_many(&[first.id, second.id]).await.unwrap(), 0);
+
+    assert_missing(&store, &first.id).await;
+    assert_eq!(db.count_rows("session").await, 2);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_many_leaves_sessions_of_other_tenants_untouched() {
+    let db = TestDb::migrated().await;
+    let globex = db.store().for_tenant("globex");
+    let mut record = active_record();
+    globex.create(&mut record).await.unwrap();
+
+    assert_eq!(db.store().for_tenant("acme").delete_many(&[record.id]).await.unwrap(), 0);
+
+    assert_loads(&globex, &record).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_many_fails_on_read_only_stores() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    store.set_read_only(true);
+
+    assert!(store.delete_many(&[record.id]).await.is_err());
+    assert!(store.delete_many(&[]).await.is_err());
+
+    assert_eq!(db.count_rows("session").await, 1);
+}
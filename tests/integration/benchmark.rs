@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use tower_sessions_seaorm_store::{BenchmarkConfig, Id};
+
+use crate::helpers::TestDb;
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn benchmark_suite_measures_every_operation_and_cleans_up() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let results = store
+        .benchmark_suite(BenchmarkConfig {
+            concurrent_clients: 4,
+            iterations_per_client: 10,
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(results.total_errors, 0);
+    for (p50, p99) in [
+        (results.create_p50_ms, results.create_p99_ms),
+        (results.load_p50_ms, results.load_p99_ms),
+        (results.save_p50_ms, results.save_p99_ms),
+        (results.delete_p50_ms, results.delete_p99_ms),
+    ] {
+        assert!(p50 > 0.0);
+        assert!(p50 <= p99);
+    }
+
+    assert_eq!(db.count_rows("session").await, 0);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn benchmark_suite_counts_failed_operations() {
+    let db = TestDb::migrated().await;
+    // Every create fails, so the rest of the cycles is skipped
+    let store = db.store().with_session_id_validator(Arc::new(|_: &Id| false));
+
+    let results = store
+        .benchmark_suite(BenchmarkConfig {
+            concurrent_clients: 2,
+            iterations_per_client: 3,
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(results.total_errors, 6);
+    assert_eq!(results.create_p99_ms, 0.0);
+    assert_eq!(results.load_p99_ms, 0.0);
+    assert_eq!(db.count_rows("session").await, 0);
+}
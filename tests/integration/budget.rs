@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use sea_orm::{ConnectOptions, ConnectionTrait, TransactionTrait};
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::{GlobalConnectionBudget, PostgresStore, SeaOrmStoreError};
+
+use crate::helpers::{active_record, TestDb};
+
+// Connecting lazily doesn't open any connection, so no database is needed
+fn lazy_options(max_connections: u32) -> ConnectOptions {
+    let mut options = ConnectOptions::new("postgres://postgres@127.0.0.1:1/sessions");
+    options.max_connections(max_connections).connect_lazy(true);
+    options
+}
+
+// The budget is process-wide, so this is the only test that installs one
+#[tokio::test]
+async fn stores_fail_once_the_connection_budget_is_used_up() {
+    let budget = GlobalConnectionBudget::new(10).unwrap();
+    assert!(matches!(
+        GlobalConnectionBudget::new(20),
+        Err(SeaOrmStoreError::ConnectionBudgetAlreadyInstalled)
+    ));
+
+    let first = PostgresStore::from_url_with_pool_config(lazy_options(6)).await.unwrap();
+    let first_clone = first.clone_with_table("tenant_a").unwrap();
+    assert_eq!(budget.remaining(), 4);
+
+    let result = PostgresStore::from_url_with_pool_config(lazy_options(6)).await;
+    assert!(matches!(
+        result,
+        Err(SeaOrmStoreError::ConnectionBudgetExceeded { requested: 6, available: 4 })
+    ));
+    assert_eq!(budget.remaining(), 4);
+
+    // Connections are returned once every clone is gone
+    drop(first);
+    assert_eq!(budget.remaining(), 4);
+    drop(first_clone);
+    assert_eq!(budget.remaining(), 10);
+
+    PostgresStore::from_url_with_pool_config(lazy_options(6)).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn connection_limit_is_shared_by_clones() {
+    let db = TestDb::migrated().await;
+    db.create_session_table("tenant_a").await;
+    let store = db.store().with_connection_pool_size_limit(1);
+    let tenant_store = store.clone_with_table("tenant_a").unwrap();
+
+    let lock = db.conn.begin().await.unwrap();
+    lock.execute_unprepared(r#"LOCK TABLE "tower_sessions"."session" IN ACCESS EXCLUSIVE MODE"#)
+        .await
+        .unwrap();
+
+    // Blocks on the lock while holding the only permit
+    let record = active_record();
+    let blocked_load = tokio::spawn({
+        let store = store.clone();
+        async move { store.load(&record.id).await }
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // The tenant table isn't locked, but the operation has to wait for the permit
+    let count = tokio::time::timeout(Duration::from_millis(200), tenant_store.count_active()).await;
+    assert!(count.is_err());
+
+    lock.rollback().await.unwrap();
+    assert!(blocked_load.await.unwrap().unwrap().is_none());
+    assert_eq!(tenant_store.count_active().await.unwrap(), 0);
+}
@@ -0,0 +1,117 @@
+use time::Duration;
+use tokio_stream::iter;
+use tower_sessions::session::Record;
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::{user_id_key, SessionDataFormat};
+
+use crate::helpers::{active_record, assert_loads, assert_missing, expired_record, TestDb};
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn bulk_import_stores_active_sessions() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let records = [active_record(), active_record(), active_record()];
+    let expired = expired_record();
+
+    let imported = store
+        .bulk_import(iter(records.iter().cloned().chain([expired.clone()])))
+        .await
+        .unwrap();
+
+    assert_eq!(imported, 3);
+    for record in &records {
+        assert_loads(&store, record).await;
+    }
+    assert_eq!(db.count_rows("session").await, 3);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn bulk_import_replaces_existing_sessions() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    record.data.insert("theme".to_string(), "light".into());
+    assert_eq!(store.bulk_import(iter([record.clone()])).await.unwrap(), 1);
+
+    assert_loads(&store, &record).await;
+    assert_eq!(db.count_rows("session").await, 1);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn bulk_import_keeps_the_duplicate_expiring_last() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let record = active_record();
+    let later = Record {
+        expiry_date: record.expiry_date + Duration::hours(1),
+        ..record.clone()
+    };
+
+    assert_eq!(store.bulk_import(iter([later.clone(), record])).await.unwrap(), 1);
+
+    assert_loads(&store, &later).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn bulk_import_quotes_text_columns() {
+    let db = TestDb::migrated().await;
+    let store = db
+        .store()
+        .with_user_id_extractor(user_id_key("user_id"))
+        .for_tenant("acme");
+    let mut record = active_record();
+    record.data.insert("user_id".to_string(), "o'brien, \"junior\"\n".into());
+
+    store.bulk_import(iter([record.clone()])).await.unwrap();
+
+    assert_loads(&store, &record).await;
+    let sessions = store.sessions_for_user("o'brien, \"junior\"\n", 0, 10).await.unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert_missing(&db.store().for_tenant("globex"), &record.id).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn bulk_import_writes_jsonb_data() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_data_format(SessionDataFormat::Jsonb);
+    store.migrate().await.unwrap();
+    let record = active_record();
+
+    store.bulk_import(iter([record.clone()])).await.unwrap();
+
+    assert_loads(&store, &record).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn bulk_import_fails_on_read_only_stores() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    store.set_read_only(true);
+
+    let result = store.bulk_import(iter([active_record()])).await;
+
+    assert!(result.is_err());
+    assert_eq!(db.count_rows("session").await, 0);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn bulk_import_stores_nothing_when_a_session_fails() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_data_size_limit_per_key(16);
+    let mut too_large = active_record();
+    too_large.data.insert("blob".to_string(), "x".repeat(64).into());
+
+    let result = store.bulk_import(iter([active_record(), too_large])).await;
+
+    assert!(result.is_err());
+    assert_eq!(db.count_rows("session").await, 0);
+}
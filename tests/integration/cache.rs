@@ -0,0 +1,177 @@
+use std::time::Duration;
+
+use sea_orm::{ConnectionTrait, TransactionTrait};
+use tower_sessions::session::Record;
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::CachedPostgresStore;
+
+use crate::helpers::{active_record, assert_loads, record, TestDb};
+
+fn cached(db: &TestDb) -> CachedPostgresStore {
+    CachedPostgresStore::new(db.store(), 100, Duration::from_secs(60))
+}
+
+fn cached_tenant(db: &TestDb, tenant_id: &str) -> CachedPostgresStore {
+    CachedPostgresStore::new(db.store().for_tenant(tenant_id), 100, Duration::from_secs(60))
+}
+
+/// Deletes every session behind the back of the cache.
+async fn delete_all_rows(db: &TestDb) {
+    db.conn
+        .execute_unprepared(r#"DELETE FROM "tower_sessions"."session""#)
+        .await
+        .unwrap();
+}
+
+async fn load(store: &CachedPostgresStore, record: &Record) -> Option<Record> {
+    store.load(&record.id).await.unwrap()
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn loads_are_served_from_the_cache() {
+    let db = TestDb::migrated().await;
+    let store = cached(&db);
+    let mut created = active_record();
+    store.create(&mut created).await.unwrap();
+
+    // Sessions loaded from the database are cached as well
+    let mut loaded = active_record();
+    db.store().create(&mut loaded).await.unwrap();
+    assert_eq!(load(&store, &loaded).await, Some(loaded.clone()));
+
+    delete_all_rows(&db).await;
+    assert_eq!(load(&store, &created).await, Some(created.clone()));
+    assert_eq!(load(&store, &loaded).await, Some(loaded.clone()));
+
+    store.invalidate(&created.id).await;
+    assert_eq!(load(&store, &created).await, None);
+    store.invalidate_all();
+    assert_eq!(load(&store, &loaded).await, None);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn saves_are_written_through() {
+    let db = TestDb::migrated().await;
+    let store = cached(&db);
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    record.data.insert("theme".to_string(), "light".into());
+    store.save(&record).await.unwrap();
+    assert_loads(&db.store(), &record).await;
+
+    delete_all_rows(&db).await;
+    assert_eq!(load(&store, &record).await, Some(record));
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn deletes_evict_the_session() {
+    let db = TestDb::migrated().await;
+    let store = cached(&db);
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    store.delete(&record.id).await.unwrap();
+    assert_eq!(load(&store, &record).await, None);
+    assert_eq!(db.count_rows("session").await, 0);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn loads_during_a_delete_do_not_cache_the_session_again() {
+    let db = TestDb::migrated().await;
+    let store = cached(&db);
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    // Lock the row so the delete blocks after evicting the session
+    let lock = db.conn.begin().await.unwrap();
+    lock.execute_unprepared(r#"SELECT id FROM "tower_sessions"."session" FOR UPDATE"#)
+        .await
+        .unwrap();
+    let delete = tokio::spawn({
+        let store = store.clone();
+        async move { store.delete(&record.id).await }
+    });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // The row is still there, so the load caches it again
+    assert_eq!(load(&store, &record).await, Some(record.clone()));
+
+    lock.rollback().await.unwrap();
+    delete.await.unwrap().unwrap();
+    assert_eq!(load(&store, &record).await, None);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn writes_the_store_may_alter_are_not_cached() {
+    let db = TestDb::migrated().await;
+    let store = CachedPostgresStore::new(
+        db.store().with_max_lifetime(time::Duration::hours(1)),
+        100,
+        Duration::from_secs(60),
+    );
+    let mut record = record(time::OffsetDateTime::now_utc() + time::Duration::days(1));
+    store.create(&mut record).await.unwrap();
+
+    // The expiry date was capped, which only the database knows
+    let loaded = load(&store, &record).await.unwrap();
+    assert!(loaded.expiry_date <= time::OffsetDateTime::now_utc() + time::Duration::hours(1));
+
+    // Saves of another tenant's session don't write anything
+    let acme = cached_tenant(&db, "acme");
+    let mut theirs = active_record();
+    cached_tenant(&db, "globex").create(&mut theirs).await.unwrap();
+    let mut forged = theirs.clone();
+    forged.data.insert("role".to_string(), "admin".into());
+    acme.save(&forged).await.unwrap();
+    assert_eq!(load(&acme, &forged).await, None);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn sessions_are_not_cached_past_their_expiry_date() {
+    let db = TestDb::migrated().await;
+    let store = cached(&db);
+    let mut record = record(time::OffsetDateTime::now_utc() + time::Duration::milliseconds(500));
+    store.create(&mut record).await.unwrap();
+    assert!(load(&store, &record).await.is_some());
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert_eq!(load(&store, &record).await, None);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn missing_ids_are_remembered_by_the_negative_cache() {
+    let db = TestDb::migrated().await;
+    let store = cached(&db).with_negative_cache(100, Duration::from_secs(60));
+    let record = active_record();
+    assert_eq!(load(&store, &record).await, None);
+
+    // The session now exists, but the ID is remembered as missing
+    db.store().save(&record).await.unwrap();
+    assert_eq!(load(&store, &record).await, None);
+
+    store.invalidate(&record.id).await;
+    assert_eq!(load(&store, &record).await, Some(record));
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn saving_a_session_forgets_that_it_was_missing() {
+    let db = TestDb::migrated().await;
+    let store = CachedPostgresStore::new(db.store(), 100, Duration::from_millis(200))
+        .with_negative_cache(100, Duration::from_secs(60));
+    let record = active_record();
+    assert_eq!(load(&store, &record).await, None);
+
+    // Once the saved session leaves the cache, it is loaded from the database again
+    store.save(&record).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    assert_eq!(load(&store, &record).await, Some(record));
+}
@@ -0,0 +1,79 @@
+use std::time::{Duration, Instant};
+
+use sea_orm::{ConnectOptions, Database};
+use tower_sessions::{session_store, SessionStore};
+use tower_sessions_seaorm_store::{CircuitBreakerConfig, CircuitState, PostgresStore};
+
+use crate::helpers::{active_record, TestDb};
+
+const CONFIG: CircuitBreakerConfig = CircuitBreakerConfig {
+    failure_threshold: 2,
+    success_threshold: 1,
+    probe_interval: Duration::from_millis(100),
+};
+
+fn is_circuit_open(result: &session_store::Result<impl Sized>) -> bool {
+    matches!(result, Err(session_store::Error::Backend(message)) if message == "circuit open")
+}
+
+#[tokio::test]
+async fn circuit_opens_after_consecutive_failures_and_fails_fast() {
+    // Nothing listens on port 1, so every connection attempt is refused right away
+    let mut options = ConnectOptions::new("postgres://postgres@127.0.0.1:1/sessions");
+    options.connect_lazy(true).acquire_timeout(Duration::from_millis(200));
+    let store = PostgresStore::new(Database::connect(options).await.unwrap()).with_circuit_breaker(CONFIG);
+    let id = active_record().id;
+
+    let first = store.load(&id).await;
+    assert!(first.is_err() && !is_circuit_open(&first));
+    assert_eq!(store.circuit_state(), Some(CircuitState::Closed { consecutive_failures: 1 }));
+    assert!(store.load(&id).await.is_err());
+    assert_eq!(store.circuit_state(), Some(CircuitState::Open));
+
+    // The pool isn't asked for a connection anymore
+    let started = Instant::now();
+    assert!(is_circuit_open(&store.load(&id).await));
+    assert!(started.elapsed() < Duration::from_millis(100));
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn circuit_half_opens_once_the_database_answers_and_closes_on_success() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_table_name("sessions_later").unwrap().with_circuit_breaker(CONFIG);
+    let id = active_record().id;
+
+    // Queries on the missing table fail
+    assert!(store.load(&id).await.is_err());
+    assert!(store.load(&id).await.is_err());
+    assert_eq!(store.circuit_state(), Some(CircuitState::Open));
+    assert!(is_circuit_open(&store.load(&id).await));
+
+    // The database still answers pings, so the probe half-opens the circuit
+    tokio::time::sleep(CONFIG.probe_interval * 3).await;
+    assert_eq!(
+        store.circuit_state(),
+        Some(CircuitState::HalfOpen { consecutive_successes: 0 })
+    );
+
+    db.create_session_table("sessions_later").await;
+    assert_eq!(store.load(&id).await.unwrap(), None);
+    assert_eq!(store.circuit_state(), Some(CircuitState::Closed { consecutive_failures: 0 }));
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn failure_while_half_open_opens_the_circuit_again() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_table_name("sessions_never").unwrap().with_circuit_breaker(CONFIG);
+    let id = active_record().id;
+
+    assert!(store.load(&id).await.is_err());
+    assert!(store.load(&id).await.is_err());
+    tokio::time::sleep(CONFIG.probe_interval * 3).await;
+    assert!(matches!(store.circuit_state(), Some(CircuitState::HalfOpen { .. })));
+
+    let result = store.load(&id).await;
+    assert!(result.is_err() && !is_circuit_open(&result));
+    assert_eq!(store.circuit_state(), Some(CircuitState::Open));
+}
@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use sea_orm::DatabaseConnection;
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::PostgresStore;
+
+use crate::helpers::{active_record, expired_record, TestDb};
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn cleanup_task_deletes_expired_sessions() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut active = active_record();
+    store.create(&mut active).await.unwrap();
+    store.create(&mut expired_record()).await.unwrap();
+
+    let cleanup = store.spawn_cleanup(Duration::from_millis(20));
+    for _ in 0..100 {
+        if db.count_rows("session").await == 1 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    cleanup.shutdown().await.unwrap();
+
+    assert_eq!(db.count_rows("session").await, 1);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn cleanup_task_deletes_expired_sessions_in_batches() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_cleanup_batch_size(1);
+    let mut active = active_record();
+    store.create(&mut active).await.unwrap();
+    for _ in 0..3 {
+        store.create(&mut expired_record()).await.unwrap();
+    }
+
+    let cleanup = store.spawn_cleanup(Duration::from_millis(20));
+    for _ in 0..100 {
+        if db.count_rows("session").await == 1 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    cleanup.shutdown().await.unwrap();
+
+    assert_eq!(db.count_rows("session").await, 1);
+}
+
+#[tokio::test]
+async fn gc_policy_reports_interval_until_shutdown() {
+    let store = PostgresStore::new(DatabaseConnection::Disconnected);
+
+    let cleanup = store.spawn_cleanup(Duration::from_secs(3600));
+    assert_eq!(
+        store.clone().gc_policy().cleanup_interval,
+        Some(time::Duration::hours(1))
+    );
+
+    // Doesn't wait for the next run
+    cleanup.shutdown().await.unwrap();
+    assert_eq!(store.gc_policy().cleanup_interval, None);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn cleanup_task_stops_once_store_is_closed() {
+    let db = TestDb::new().await;
+    let store = db.store();
+    let cleanup = store.spawn_cleanup(Duration::from_millis(10));
+
+    store.close().await.unwrap();
+    for _ in 0..100 {
+        if cleanup.is_finished() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    assert!(cleanup.is_finished());
+}
+
+#[test]
+#[should_panic(expected = "cleanup interval must be non-zero")]
+fn zero_cleanup_interval_panics() {
+    PostgresStore::new(DatabaseConnection::Disconnected).spawn_cleanup(Duration::ZERO);
+}
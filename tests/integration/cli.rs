@@ -0,0 +1,55 @@
+use std::process::{Command, Output};
+
+use tower_sessions::SessionStore;
+
+use crate::helpers::{active_record, assert_missing, expired_record, TestDb};
+
+/// Runs the `sessions` binary against the database of `db`.
+fn sessions(db: &TestDb, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_sessions"))
+        .env("DATABASE_URL", &db.url)
+        .args(args)
+        .output()
+        .expect("failed to run the sessions binary")
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn cli_migrates_counts_and_purges_sessions() {
+    let db = TestDb::new().await;
+    stdout(&sessions(&db, &["migrate"]));
+
+    let store = db.store();
+    let mut active = active_record();
+    let mut expired = expired_record();
+    store.create(&mut active).await.unwrap();
+    store.create(&mut expired).await.unwrap();
+
+    assert_eq!(stdout(&sessions(&db, &["count"])), "active: 1\nexpired: 1\n");
+    assert_eq!(stdout(&sessions(&db, &["purge-expired"])), "deleted: 1\n");
+    assert_eq!(db.count_rows("session").await, 1);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn cli_inspects_and_deletes_sessions() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut record = active_record();
+    record.data.insert("user_id".to_string(), 42.into());
+    store.create(&mut record).await.unwrap();
+    let id = record.id.to_string();
+
+    let session: serde_json::Value = serde_json::from_str(&stdout(&sessions(&db, &["inspect", &id]))).unwrap();
+    assert_eq!(session["id"], id.as_str());
+    assert_eq!(session["data"]["user_id"], 42);
+
+    stdout(&sessions(&db, &["delete", &id]));
+    assert_missing(&store, &record.id).await;
+    assert!(!sessions(&db, &["inspect", &id]).status.success());
+}
@@ -0,0 +1,106 @@
+use std::net::{IpAddr, Ipv4Addr};
+
+use axum::body::Body;
+use axum::http::Request;
+use axum::routing::post;
+use axum::Router;
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+use tower_layer::Layer;
+use tower_service::Service;
+use tower_sessions::session::{Id, Record};
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::{user_id_key, ClientMetadata, ClientMetadataLayer, PostgresStore};
+
+use crate::helpers::{active_record, TestDb};
+
+/// Returns the `ip_address` and `user_agent` columns of session `id`.
+async fn stored_metadata(db: &TestDb, id: &Id) -> (Option<String>, Option<String>) {
+    let row = db
+        .conn
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"SELECT "ip_address", "user_agent" FROM "tower_sessions"."session" WHERE "id" = $1"#,
+            [id.to_string().into()],
+        ))
+        .await
+        .unwrap()
+        .expect("session row not found");
+
+    (row.try_get("", "ip_address").unwrap(), row.try_get("", "user_agent").unwrap())
+}
+
+fn metadata() -> ClientMetadata {
+    ClientMetadata {
+        ip_address: Some(IpAddr::V4(Ipv4Addr::new(203, 0, 113, 7))),
+        user_agent: Some("Mozilla/5.0".to_string()),
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn writes_record_metadata_of_the_scope() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_user_id_extractor(user_id_key("user_id"));
+
+    let mut record = active_record();
+    metadata().scope(store.create(&mut record)).await.unwrap();
+    assert_eq!(
+        stored_metadata(&db, &record.id).await,
+        (Some("203.0.113.7".to_string()), Some("Mozilla/5.0".to_string()))
+    );
+
+    // The record holds `"user_id": 42`
+    let sessions = store.sessions_for_user("42", 0, 10).await.unwrap();
+    assert_eq!(sessions[0].ip_address, metadata().ip_address);
+    assert_eq!(sessions[0].user_agent, metadata().user_agent);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn writes_outside_a_scope_keep_metadata() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    assert_eq!(stored_metadata(&db, &record.id).await, (None, None));
+
+    metadata().scope(store.save(&record)).await.unwrap();
+    store.save(&record).await.unwrap();
+    assert_eq!(
+        stored_metadata(&db, &record.id).await,
+        (Some("203.0.113.7".to_string()), Some("Mozilla/5.0".to_string()))
+    );
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn layer_records_forwarded_for_and_user_agent() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    let handler = {
+        let (store, record): (PostgresStore, Record) = (store.clone(), record.clone());
+        move || async move { store.save(&record).await.unwrap() }
+    };
+    let router: Router = Router::new().route("/", post(handler));
+    let mut service = ClientMetadataLayer::new().with_forwarded_for().layer(router);
+
+    let request = Request::post("/")
+        .header("user-agent", "curl/8.0")
+        .header("x-forwarded-for", "198.51.100.1, 10.0.0.1")
+        .body(Body::empty())
+        .unwrap();
+    std::future::poll_fn(|cx| Service::<Request<Body>>::poll_ready(&mut service, cx))
+        .await
+        .unwrap();
+    service.call(request).await.unwrap();
+
+    assert_eq!(
+        stored_metadata(&db, &record.id).await,
+        (Some("198.51.100.1".to_string()), Some("curl/8.0".to_string()))
+    );
+}
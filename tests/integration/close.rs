@@ -0,0 +1,28 @@
+use tower_sessions::{session_store, ExpiredDeletion, SessionStore};
+
+use crate::helpers::{active_record, TestDb};
+
+fn is_closed_error<T>(result: session_store::Result<T>) -> bool {
+    matches!(result, Err(session_store::Error::Backend(message)) if message == "store is closed")
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn operations_fail_after_close() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let clone = store.clone();
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    store.close().await.unwrap();
+    assert!(store.is_closed() && clone.is_closed());
+
+    assert!(is_closed_error(store.load(&record.id).await));
+    assert!(is_closed_error(clone.save(&record).await));
+    assert!(is_closed_error(clone.delete_expired().await));
+    assert!(store.migrate().await.is_err());
+
+    // Closing again is harmless
+    store.close().await.unwrap();
+}
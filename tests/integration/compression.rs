@@ -0,0 +1,65 @@
+use tokio_stream::StreamExt;
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::PayloadCodec;
+
+use crate::helpers::{active_record, assert_loads, TestDb};
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn large_sessions_are_stored_compressed() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut record = active_record();
+    record.data.insert("blob".to_string(), "abc".repeat(4096).into());
+
+    store.create(&mut record).await.unwrap();
+    assert_loads(&store, &record).await;
+
+    let stored = db.stored_data(&record.id.to_string()).await;
+    assert_eq!(stored[0], 0xC1);
+    assert!(stored.len() < rmp_serde::to_vec(&record).unwrap().len() / 10);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn small_and_uncompressed_sessions_are_stored_as_is() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    // Too small for compression to pay off
+    let mut small = active_record();
+    store.create(&mut small).await.unwrap();
+    assert_eq!(db.stored_data(&small.id.to_string()).await, [&[0xC3][..], &rmp_serde::to_vec(&small).unwrap()].concat());
+
+    // Written before compression was enabled
+    let mut legacy = active_record();
+    legacy.data.insert("blob".to_string(), "abc".repeat(4096).into());
+    store
+        .upsert_raw(&legacy.id, rmp_serde::to_vec(&legacy).unwrap(), legacy.expiry_date)
+        .await
+        .unwrap();
+    assert_loads(&store, &legacy).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn reencode_all_compresses_uncompressed_sessions() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut record = active_record();
+    record.data.insert("blob".to_string(), "abc".repeat(4096).into());
+    store
+        .upsert_raw(&record.id, rmp_serde::to_vec(&record).unwrap(), record.expiry_date)
+        .await
+        .unwrap();
+
+    let progress = store
+        .reencode_all(PayloadCodec::MessagePack, PayloadCodec::Zstd, 100)
+        .collect::<Result<Vec<_>, _>>()
+        .await
+        .unwrap();
+
+    assert_eq!(progress.last().unwrap().reencoded, 1);
+    assert_eq!(db.stored_data(&record.id.to_string()).await[0], 0xC1);
+    assert_loads(&store, &record).await;
+}
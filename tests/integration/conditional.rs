@@ -0,0 +1,191 @@
+use time::{Duration, OffsetDateTime};
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::{ConditionalSaveExpr, SeaOrmStoreError};
+
+use crate::helpers::{active_record, assert_loads, assert_missing, TestDb};
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn only_if_exists_updates_existing_session_only() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let record = active_record();
+    let saved = store
+        .conditional_save(&record, ConditionalSaveExpr::OnlyIfExists)
+        .await
+        .unwrap();
+    assert!(!saved);
+    assert_missing(&store, &record.id).await;
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    record.data.insert("theme".to_string(), "light".into());
+    let saved = store
+        .conditional_save(&record, ConditionalSaveExpr::OnlyIfExists)
+        .await
+        .unwrap();
+    assert!(saved);
+    assert_loads(&store, &record).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn only_if_not_exists_inserts_new_session_only() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut record = active_record();
+    let saved = store
+        .conditional_save(&record, ConditionalSaveExpr::OnlyIfNotExists)
+        .await
+        .unwrap();
+    assert!(saved);
+    assert_loads(&store, &record).await;
+
+    let original = record.clone();
+    record.data.insert("theme".to_string(), "light".into());
+    let saved = store
+        .conditional_save(&record, ConditionalSaveExpr::OnlyIfNotExists)
+        .await
+        .unwrap();
+    assert!(!saved);
+    assert_loads(&store, &original).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn only_if_expiry_less_than_never_shortens_session() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    // Extending the session is applied
+    let mut extended = record.clone();
+    extended.expiry_date = record.expiry_date + Duration::days(1);
+    let saved = store
+        .conditional_save(
+            &extended,
+            ConditionalSaveExpr::OnlyIfExpiryLessThan(extended.expiry_date),
+        )
+        .await
+        .unwrap();
+    assert!(saved);
+
+    // Shortening it again is not
+    let mut shortened = record.clone();
+    shortened.expiry_date = OffsetDateTime::now_utc() + Duration::hours(1);
+    let saved = store
+        .conditional_save(
+            &shortened,
+            ConditionalSaveExpr::OnlyIfExpiryLessThan(shortened.expiry_date),
+        )
+        .await
+        .unwrap();
+    assert!(!saved);
+    assert_loads(&store, &extended).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn only_if_version_equals_detects_concurrent_update() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    let version = store.session_version(&record.id).await.unwrap().unwrap();
+    assert_eq!(version, 0);
+
+    // Another request updates the session in the meantime
+    store.save(&record).await.unwrap();
+    assert_eq!(store.session_version(&record.id).await.unwrap(), Some(1));
+
+    record.data.insert("theme".to_string(), "light".into());
+    let saved = store
+        .conditional_save(&record, ConditionalSaveExpr::OnlyIfVersionEquals(version))
+        .await
+        .unwrap();
+    assert!(!saved);
+
+    let saved = store
+        .conditional_save(&record, ConditionalSaveExpr::OnlyIfVersionEquals(1))
+        .await
+        .unwrap();
+    assert!(saved);
+    assert_eq!(store.session_version(&record.id).await.unwrap(), Some(2));
+    assert_loads(&store, &record).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn upsert_raw_increments_version() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let record = active_record();
+    let raw_bytes = rmp_serde::to_vec(&record).unwrap();
+    store
+        .upsert_raw(&record.id, raw_bytes.clone(), record.expiry_date)
+        .await
+        .unwrap();
+    store
+        .upsert_raw(&record.id, raw_bytes, record.expiry_date)
+        .await
+        .unwrap();
+
+    assert_eq!(store.session_version(&record.id).await.unwrap(), Some(1));
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn save_versioned_returns_new_version() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    record.data.insert("theme".to_string(), "light".into());
+    assert_eq!(store.save_versioned(&record, 0).await.unwrap(), 1);
+    assert_eq!(store.save_versioned(&record, 1).await.unwrap(), 2);
+    assert_eq!(store.session_version(&record.id).await.unwrap(), Some(2));
+    assert_loads(&store, &record).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn save_versioned_conflicts_when_version_advanced() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    let stale = record.clone();
+
+    // Another request updates the session in the meantime
+    record.data.insert("theme".to_string(), "light".into());
+    store.save(&record).await.unwrap();
+
+    let err = store.save_versioned(&stale, 0).await.unwrap_err();
+    assert!(matches!(err, SeaOrmStoreError::Conflict { expected: 0 }));
+    assert_eq!(store.session_version(&record.id).await.unwrap(), Some(1));
+    assert_loads(&store, &record).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn save_versioned_conflicts_when_deleted() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    store.delete(&record.id).await.unwrap();
+
+    let err = store.save_versioned(&record, 0).await.unwrap_err();
+    assert!(matches!(err, SeaOrmStoreError::Conflict { expected: 0 }));
+    assert_missing(&store, &record.id).await;
+}
@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use sea_orm::{ConnectionTrait, DatabaseConnection};
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::PostgresStore;
+
+use crate::helpers::{active_record, TestDb};
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn store_shares_the_connection_pool_of_the_application() {
+    let db = TestDb::migrated().await;
+    // The application keeps its connection behind an `Arc`
+    let app_conn = Arc::new(db.conn.clone());
+    let store = PostgresStore::new(DatabaseConnection::clone(&app_conn));
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    assert_eq!(db.count_rows("session").await, 1);
+
+    // Stores on clones of one pool are equal, whichever clone they were created from
+    assert_eq!(store, PostgresStore::new(store.connection().clone()));
+    store
+        .connection()
+        .execute_unprepared(r#"DELETE FROM "tower_sessions"."session""#)
+        .await
+        .unwrap();
+    assert_eq!(store.load(&record.id).await.unwrap(), None);
+}
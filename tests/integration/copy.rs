@@ -0,0 +1,70 @@
+use tower_sessions::session::Id;
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::SeaOrmStoreError;
+
+use crate::helpers::{active_record, assert_loads, assert_missing, expired_record, TestDb};
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn copy_session_creates_independent_copy() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut original = active_record();
+    store.create(&mut original).await.unwrap();
+
+    let copy_id = Id::default();
+    store.copy_session(&original.id, &copy_id).await.unwrap();
+
+    let mut copy = original.clone();
+    copy.id = copy_id;
+    assert_loads(&store, &copy).await;
+
+    // Changing the copy leaves the original untouched
+    copy.data.insert("theme".to_string(), "light".into());
+    store.save(&copy).await.unwrap();
+    assert_loads(&store, &original).await;
+    assert_loads(&store, &copy).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn copy_session_fails_for_missing_or_expired_source() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let copy_id = Id::default();
+    assert!(matches!(
+        store.copy_session(&Id::default(), &copy_id).await,
+        Err(SeaOrmStoreError::SessionNotFound)
+    ));
+
+    let mut expired = expired_record();
+    store.create(&mut expired).await.unwrap();
+    assert!(matches!(
+        store.copy_session(&expired.id, &copy_id).await,
+        Err(SeaOrmStoreError::SessionNotFound)
+    ));
+
+    assert_missing(&store, &copy_id).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn copy_session_does_not_overwrite_existing_session() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut source = active_record();
+    let mut target = active_record();
+    target.data.insert("theme".to_string(), "light".into());
+    store.create(&mut source).await.unwrap();
+    store.create(&mut target).await.unwrap();
+
+    assert!(matches!(
+        store.copy_session(&source.id, &target.id).await,
+        Err(SeaOrmStoreError::SessionAlreadyExists)
+    ));
+
+    assert_loads(&store, &target).await;
+}
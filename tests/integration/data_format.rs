@@ -0,0 +1,74 @@
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+use tower_sessions::session::{Id, Record};
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::SessionDataFormat;
+
+use crate::helpers::{active_record, assert_loads, assert_missing, TestDb};
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn jsonb_sessions_round_trip_and_are_queryable_with_sql() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_data_format(SessionDataFormat::Jsonb);
+    store.migrate().await.unwrap();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    assert_loads(&store, &record).await;
+
+    record.data.insert("theme".to_string(), "light".into());
+    store.save(&record).await.unwrap();
+    assert_loads(&store, &record).await;
+
+    let row = db
+        .conn
+        .query_one(Statement::from_string(
+            DbBackend::Postgres,
+            r#"SELECT id FROM "tower_sessions"."session" WHERE data->'data'->>'theme' = 'light'"#,
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(row.try_get::<String>("", "id").unwrap(), record.id.to_string());
+
+    let copy_id = Id::default();
+    store.copy_session(&record.id, &copy_id).await.unwrap();
+    store.delete(&record.id).await.unwrap();
+    assert_missing(&store, &record.id).await;
+    assert_loads(&store, &Record { id: copy_id, ..record }).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn jsonb_migration_runs_once() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_data_format(SessionDataFormat::Jsonb);
+    store.migrate().await.unwrap();
+    store.migrate().await.unwrap();
+
+    let row = db
+        .conn
+        .query_one(Statement::from_string(
+            DbBackend::Postgres,
+            "SELECT data_type FROM information_schema.columns \
+             WHERE table_schema = 'tower_sessions' AND table_name = 'session' AND column_name = 'data'",
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(row.try_get::<String>("", "data_type").unwrap(), "jsonb");
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn jsonb_migration_fails_on_messagepack_sessions() {
+    let db = TestDb::migrated().await;
+    let mut record = active_record();
+    db.store().create(&mut record).await.unwrap();
+
+    let store = db.store().with_data_format(SessionDataFormat::Jsonb);
+    assert!(store.migrate().await.is_err());
+
+    // The failed migration left the MessagePack session in place
+    assert_loads(&db.store(), &record).await;
+}
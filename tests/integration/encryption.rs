@@ -0,0 +1,189 @@
+use std::sync::Arc;
+
+use tower_sessions::{session_store, SessionStore};
+use tower_sessions_seaorm_store::{KeyRing, SeaOrmStoreError, SessionDataFormat, StaticKeyProvider};
+
+use crate::helpers::{active_record, assert_loads, TestDb};
+
+const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+const NEW_KEY: &str = "202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f";
+
+fn key_provider(hex: &str) -> Arc<StaticKeyProvider> {
+    Arc::new(StaticKeyProvider::from_hex(hex).unwrap())
+}
+
+/// Returns a key ring holding `KEY` with ID 1 and, if `rotated`, `NEW_KEY` with ID 2.
+fn key_ring(rotated: bool) -> Arc<KeyRing> {
+    let keys = KeyRing::new().with_key(1, StaticKeyProvider::from_hex(KEY).unwrap());
+    if rotated {
+        Arc::new(keys.with_key(2, StaticKeyProvider::from_hex(NEW_KEY).unwrap()))
+    } else {
+        Arc::new(keys)
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn sessions_are_stored_encrypted() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_encryption(key_provider(KEY));
+    let mut record = active_record();
+
+    store.create(&mut record).await.unwrap();
+    assert_loads(&store, &record).await;
+
+    let stored = db.stored_data(&record.id.to_string()).await;
+    assert_eq!(stored[..2], [0xC2, 0]);
+    assert!(!stored.windows(4).any(|window| window == b"dark"));
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn plaintext_sessions_are_rejected() {
+    let db = TestDb::migrated().await;
+    let mut record = active_record();
+
+    // A session forged by writing it to the table unencrypted
+    db.store().create(&mut record).await.unwrap();
+
+    let store = db.store().with_encryption(key_provider(KEY));
+    assert!(matches!(store.load(&record.id).await, Err(session_store::Error::Decode(_))));
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn plaintext_sessions_load_with_the_plaintext_fallback() {
+    let db = TestDb::migrated().await;
+    let mut record = active_record();
+    db.store().create(&mut record).await.unwrap();
+
+    let store = db
+        .store()
+        .with_encryption(key_provider(KEY))
+        .with_plaintext_fallback(true);
+    assert_loads(&store, &record).await;
+
+    // Saving encrypts the session
+    store.save(&record).await.unwrap();
+    assert_eq!(db.stored_data(&record.id.to_string()).await[0], 0xC2);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn sessions_do_not_load_with_another_key() {
+    let db = TestDb::migrated().await;
+    let mut record = active_record();
+    db.store().with_encryption(key_provider(KEY)).create(&mut record).await.unwrap();
+
+    let other_key = "ff".repeat(32);
+    let store = db.store().with_encryption(key_provider(&other_key));
+    assert!(matches!(store.load(&record.id).await, Err(session_store::Error::Decode(_))));
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn encrypted_data_does_not_load_in_another_row() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_encryption(key_provider(KEY));
+    let mut victim = active_record();
+    store.create(&mut victim).await.unwrap();
+    let mut attacker = active_record();
+    store.create(&mut attacker).await.unwrap();
+
+    // Swap the ciphertext of the attacker's session into the victim's row
+    let data = db.stored_data(&attacker.id.to_string()).await;
+    db.store().upsert_raw(&victim.id, data, victim.expiry_date).await.unwrap();
+
+    assert!(matches!(store.load(&victim.id).await, Err(session_store::Error::Decode(_))));
+    assert_loads(&store, &attacker).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn encryption_requires_message_pack_format() {
+    let db = TestDb::migrated().await;
+    let store = db
+        .store()
+        .with_data_format(SessionDataFormat::Jsonb)
+        .with_encryption(key_provider(KEY));
+
+    let mut record = active_record();
+    assert!(matches!(store.create(&mut record).await, Err(session_store::Error::Encode(_))));
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn new_writes_use_the_newest_key() {
+    let db = TestDb::migrated().await;
+    let mut record = active_record();
+    db.store().with_encryption(key_ring(false)).create(&mut record).await.unwrap();
+    assert_eq!(db.stored_data(&record.id.to_string()).await[..2], [0xC2, 1]);
+
+    // Sessions encrypted with the previous key still load
+    let store = db.store().with_encryption(key_ring(true));
+    assert_loads(&store, &record).await;
+
+    store.save(&record).await.unwrap();
+    assert_eq!(db.stored_data(&record.id.to_string()).await[..2], [0xC2, 2]);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn rewrap_all_reencrypts_sessions_with_the_newest_key() {
+    let db = TestDb::migrated().await;
+    let mut plaintext = active_record();
+    db.store().create(&mut plaintext).await.unwrap();
+    let mut encrypted = active_record();
+    db.store().with_encryption(key_ring(false)).create(&mut encrypted).await.unwrap();
+    let mut current = active_record();
+    db.store().with_encryption(key_ring(true)).create(&mut current).await.unwrap();
+
+    let store = db.store().with_encryption(key_ring(true)).with_plaintext_fallback(true);
+    assert_eq!(store.rewrap_all().await.unwrap(), 2);
+    assert_eq!(store.rewrap_all().await.unwrap(), 0);
+
+    // The previous key is no longer needed
+    let keys = KeyRing::new().with_key(2, StaticKeyProvider::from_hex(NEW_KEY).unwrap());
+    let store = db.store().with_encryption(Arc::new(keys));
+    for record in [&plaintext, &encrypted, &current] {
+        assert_eq!(db.stored_data(&record.id.to_string()).await[..2], [0xC2, 2]);
+        assert_loads(&store, record).await;
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn rewrap_all_only_reencrypts_sessions_of_the_tenant() {
+    let db = TestDb::migrated().await;
+    let acme = db.store().for_tenant("acme");
+    let globex = db.store().for_tenant("globex");
+    let mut ours = active_record();
+    acme.clone().with_encryption(key_ring(false)).create(&mut ours).await.unwrap();
+    let mut theirs = active_record();
+    globex.with_encryption(key_ring(false)).create(&mut theirs).await.unwrap();
+
+    assert_eq!(acme.with_encryption(key_ring(true)).rewrap_all().await.unwrap(), 1);
+
+    assert_eq!(db.stored_data(&ours.id.to_string()).await[..2], [0xC2, 2]);
+    assert_eq!(db.stored_data(&theirs.id.to_string()).await[..2], [0xC2, 1]);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn rewrap_all_requires_encryption() {
+    let db = TestDb::migrated().await;
+    assert!(matches!(db.store().rewrap_all().await, Err(session_store::Error::Backend(_))));
+}
+
+#[test]
+fn hex_keys_must_have_256_bits() {
+    assert!(StaticKeyProvider::from_hex(KEY).is_ok());
+    assert!(StaticKeyProvider::from_hex(&format!("{KEY}\n")).is_ok());
+
+    for invalid in ["", "00", &KEY[2..], &format!("{}zz", &KEY[2..]), &"é".repeat(32)] {
+        assert!(matches!(
+            StaticKeyProvider::from_hex(invalid),
+            Err(SeaOrmStoreError::EncryptionKey(_))
+        ));
+    }
+}
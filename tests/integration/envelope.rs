@@ -0,0 +1,66 @@
+use tower_sessions::session::Record;
+use tower_sessions::{session_store, SessionStore};
+use tower_sessions_seaorm_store::PayloadVersion;
+
+use crate::helpers::{active_record, assert_loads, TestDb};
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn payloads_are_tagged_with_their_codec() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut record = active_record();
+
+    store.create(&mut record).await.unwrap();
+
+    let stored = db.stored_data(&record.id.to_string()).await;
+    assert_eq!(stored[0], 0xC3);
+    assert_eq!(rmp_serde::from_slice::<Record>(&stored[1..]).unwrap(), record);
+    assert_loads(&store, &record).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn untagged_payloads_of_older_versions_load() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let record = active_record();
+
+    store
+        .upsert_raw(&record.id, rmp_serde::to_vec(&record).unwrap(), record.expiry_date)
+        .await
+        .unwrap();
+
+    assert_loads(&store, &record).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn untagged_version_writes_plain_message_pack() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_payload_version(PayloadVersion::Untagged);
+    let mut record = active_record();
+
+    store.create(&mut record).await.unwrap();
+
+    assert_eq!(db.stored_data(&record.id.to_string()).await, rmp_serde::to_vec(&record).unwrap());
+    assert_loads(&db.store(), &record).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn unknown_tags_fail_to_decode() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let record = active_record();
+    // A codec added by a newer version of the application
+    let payload = [&[0xD4][..], &rmp_serde::to_vec(&record).unwrap()].concat();
+    store.upsert_raw(&record.id, payload, record.expiry_date).await.unwrap();
+
+    let result = store.load(&record.id).await;
+
+    assert!(
+        matches!(&result, Err(session_store::Error::Decode(message)) if message == "unsupported session payload tag 0xD4"),
+        "{result:?}"
+    );
+}
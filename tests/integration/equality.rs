@@ -0,0 +1,46 @@
+use std::collections::HashSet;
+
+use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use tower_sessions_seaorm_store::PostgresStore;
+
+// Connecting lazily doesn't open any connection, so no database is needed
+async fn lazy_connection() -> DatabaseConnection {
+    let mut options = ConnectOptions::new("postgres://postgres@127.0.0.1:1/sessions");
+    options.connect_lazy(true);
+    Database::connect(options).await.unwrap()
+}
+
+#[tokio::test]
+async fn stores_sharing_a_pool_and_table_are_equal() {
+    let conn = lazy_connection().await;
+    let store = PostgresStore::new(conn.clone());
+
+    assert_eq!(store, PostgresStore::new(conn.clone()));
+    assert_eq!(store, store.clone().with_idle_session_ttl(time::Duration::minutes(5)));
+    assert_ne!(store, store.clone_with_table("tenant_a_sessions").unwrap());
+    assert_ne!(store, PostgresStore::new(conn).with_schema_name("myapp").unwrap());
+}
+
+#[tokio::test]
+async fn stores_with_separate_pools_are_not_equal() {
+    let first = PostgresStore::new(lazy_connection().await);
+    let second = PostgresStore::new(lazy_connection().await);
+
+    assert_ne!(first, second);
+}
+
+#[tokio::test]
+// The store has shared mutable state, but the hash only covers the pool and the table
+#[allow(clippy::mutable_key_type)]
+async fn equal_stores_are_deduplicated_in_hash_sets() {
+    let conn = lazy_connection().await;
+    let store = PostgresStore::new(conn.clone());
+
+    let stores = HashSet::from([
+        store.clone(),
+        PostgresStore::new(conn),
+        store.clone_with_table("tenant_a_sessions").unwrap(),
+        PostgresStore::new(lazy_connection().await),
+    ]);
+    assert_eq!(stores.len(), 3);
+}
@@ -0,0 +1,41 @@
+use tower_sessions::session_store;
+use tower_sessions_seaorm_store::SeaOrmStoreError;
+
+// Error conversions are pure, so no database is needed
+
+fn round_trip(err: session_store::Error) -> session_store::Error {
+    SeaOrmStoreError::from(err).into()
+}
+
+#[test]
+fn session_store_errors_survive_a_round_trip() {
+    let err = round_trip(session_store::Error::Backend("connection refused".to_string()));
+    assert!(matches!(err, session_store::Error::Backend(message) if message == "connection refused"));
+
+    let err = round_trip(session_store::Error::Encode("value too large".to_string()));
+    assert!(matches!(err, session_store::Error::Encode(message) if message == "value too large"));
+
+    let err = round_trip(session_store::Error::Decode("unexpected end of input".to_string()));
+    assert!(matches!(err, session_store::Error::Decode(message) if message == "unexpected end of input"));
+}
+
+#[test]
+fn session_store_errors_map_to_matching_variants() {
+    let err = SeaOrmStoreError::from(session_store::Error::Backend("connection refused".to_string()));
+    assert!(matches!(err, SeaOrmStoreError::SeaOrm(sea_orm::DbErr::Custom(message)) if message == "connection refused"));
+
+    let err = SeaOrmStoreError::from(session_store::Error::Encode("value too large".to_string()));
+    assert!(matches!(err, SeaOrmStoreError::Encode(message) if message == "value too large"));
+
+    let err = SeaOrmStoreError::from(session_store::Error::Decode("unexpected end of input".to_string()));
+    assert!(matches!(err, SeaOrmStoreError::Decode(message) if message == "unexpected end of input"));
+}
+
+#[test]
+fn messagepack_errors_keep_their_message() {
+    let decode_err = rmp_serde::from_slice::<String>(b"").unwrap_err();
+    let message = decode_err.to_string();
+
+    let err: session_store::Error = SeaOrmStoreError::from(decode_err).into();
+    assert!(matches!(err, session_store::Error::Decode(decoded) if decoded == message));
+}
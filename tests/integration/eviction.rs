@@ -0,0 +1,97 @@
+use time::Duration;
+use tower_sessions::session::Record;
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::{user_id_key, EvictionPolicy, PostgresStore};
+
+use crate::helpers::{active_record, assert_loads, assert_missing, TestDb};
+
+/// Returns a store keeping at most `max` sessions per user by `policy`.
+fn limited_store(db: &TestDb, max: u64, policy: EvictionPolicy) -> PostgresStore {
+    db.store()
+        .with_user_id_extractor(user_id_key("user_id"))
+        .with_max_sessions_per_user(max, policy)
+}
+
+/// Creates a session of user `user_id` with `store`.
+async fn create_session_of(store: &PostgresStore, user_id: &str) -> Record {
+    let mut record = active_record();
+    record.data.insert("user_id".to_string(), user_id.into());
+    store.create(&mut record).await.unwrap();
+    record
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn creating_a_session_evicts_the_oldest_beyond_the_limit() {
+    let db = TestDb::migrated().await;
+    let store = limited_store(&db, 2, EvictionPolicy::OldestFirst);
+    let first = create_session_of(&store, "alice").await;
+    let second = create_session_of(&store, "alice").await;
+    let other = create_session_of(&store, "bob").await;
+
+    let third = create_session_of(&store, "alice").await;
+
+    assert_missing(&store, &first.id).await;
+    for record in [&second, &third, &other] {
+        assert_loads(&store, record).await;
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn least_recently_used_sessions_are_evicted_first() {
+    let db = TestDb::migrated().await;
+    let store = limited_store(&db, 2, EvictionPolicy::LeastRecentlyUsed);
+    let first = create_session_of(&store, "alice").await;
+    let second = create_session_of(&store, "alice").await;
+    // Using the first session again makes the second the least recently used
+    store.save(&first).await.unwrap();
+
+    let third = create_session_of(&store, "alice").await;
+
+    assert_missing(&store, &second.id).await;
+    assert_loads(&store, &first).await;
+    assert_loads(&store, &third).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn saving_a_session_of_a_user_enforces_the_limit() {
+    let db = TestDb::migrated().await;
+    let unlimited = db.store().with_user_id_extractor(user_id_key("user_id"));
+    for _ in 0..3 {
+        create_session_of(&unlimited, "alice").await;
+    }
+    let store = limited_store(&db, 1, EvictionPolicy::LeastRecentlyUsed);
+    let mut anonymous = active_record();
+    anonymous.data.remove("user_id");
+    store.create(&mut anonymous).await.unwrap();
+
+    // Logging in on the anonymous session
+    anonymous.data.insert("user_id".to_string(), "alice".into());
+    store.save(&anonymous).await.unwrap();
+
+    assert_eq!(db.count_rows("session").await, 1);
+    assert_loads(&store, &anonymous).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn evicted_sessions_are_marked_as_deleted_in_soft_delete_mode() {
+    let db = TestDb::migrated().await;
+    let store = limited_store(&db, 1, EvictionPolicy::OldestFirst).with_soft_delete(Duration::days(30));
+    let first = create_session_of(&store, "alice").await;
+
+    let second = create_session_of(&store, "alice").await;
+
+    assert_missing(&store, &first.id).await;
+    assert_loads(&store, &second).await;
+    assert_eq!(db.count_rows("session").await, 2);
+}
+
+#[test]
+#[should_panic(expected = "at least one session")]
+fn a_limit_of_zero_sessions_is_rejected() {
+    let _ = PostgresStore::new(sea_orm::DatabaseConnection::Disconnected)
+        .with_max_sessions_per_user(0, EvictionPolicy::OldestFirst);
+}
@@ -0,0 +1,65 @@
+use time::{Duration, OffsetDateTime};
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::SeaOrmStoreError;
+
+use crate::helpers::{active_record, assert_loads, assert_missing, expired_record, record, TestDb};
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn exported_sessions_can_be_imported_into_another_database() {
+    let source = TestDb::migrated().await;
+    let mut first = active_record();
+    let mut second = active_record();
+    second.data.insert("cart".to_string(), serde_json::json!({ "items": [1, 2, 3] }));
+    let mut expired = expired_record();
+    for record in [&mut first, &mut second, &mut expired] {
+        source.store().create(record).await.unwrap();
+    }
+
+    let mut dump = Vec::new();
+    assert_eq!(source.store().export(&mut dump).await.unwrap(), 2);
+    assert_eq!(dump.iter().filter(|byte| **byte == b'\n').count(), 2);
+
+    let target = TestDb::migrated().await;
+    let store = target.store();
+    assert_eq!(store.import(dump.as_slice()).await.unwrap(), 2);
+    assert_loads(&store, &first).await;
+    assert_loads(&store, &second).await;
+    assert_missing(&store, &expired.id).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn import_overwrites_existing_sessions_and_skips_expired_ones() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut kept = active_record();
+    let mut expiring = record(OffsetDateTime::now_utc() + Duration::milliseconds(500));
+    store.create(&mut kept).await.unwrap();
+    store.create(&mut expiring).await.unwrap();
+
+    let mut dump = Vec::new();
+    assert_eq!(store.export(&mut dump).await.unwrap(), 2);
+    let mut changed = kept.clone();
+    changed.data.insert("theme".to_string(), "light".into());
+    store.save(&changed).await.unwrap();
+    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+    assert_eq!(store.import(dump.as_slice()).await.unwrap(), 1);
+    assert_loads(&store, &kept).await;
+    assert_missing(&store, &expiring.id).await;
+}
+
+#[tokio::test]
+async fn import_reports_the_line_of_invalid_sessions() {
+    let store = tower_sessions_seaorm_store::PostgresStore::new(sea_orm::DatabaseConnection::Disconnected);
+    let expired = record(OffsetDateTime::now_utc() - Duration::days(1));
+    let dump = format!(
+        "{{\"id\":\"{}\",\"expiry_date\":\"{}\",\"data\":\"gA==\"}}\nnot json\n",
+        expired.id,
+        expired.expiry_date.format(&time::format_description::well_known::Rfc3339).unwrap(),
+    );
+
+    let err = store.import(dump.as_bytes()).await.unwrap_err();
+    assert!(matches!(&err, SeaOrmStoreError::Decode(message) if message.starts_with("line 2:")), "{err}");
+}
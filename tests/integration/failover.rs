@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::PostgresStore;
+
+use crate::helpers::{active_record, assert_loads, TestDb};
+
+// Nothing listens on port 1, so every connection attempt is refused right away
+async fn unreachable_connection() -> DatabaseConnection {
+    let mut options = ConnectOptions::new("postgres://postgres@127.0.0.1:1/sessions");
+    options.connect_lazy(true).acquire_timeout(Duration::from_secs(1));
+    Database::connect(options).await.unwrap()
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn unreachable_primary_fails_over_to_the_secondary() {
+    let secondary = TestDb::migrated().await;
+    let store = PostgresStore::new(unreachable_connection().await)
+        .with_failover(secondary.conn.clone(), Duration::ZERO);
+    let clone = store.clone();
+    assert!(!store.is_using_failover());
+
+    // The operation that detects the outage still fails
+    let mut record = active_record();
+    assert!(store.create(&mut record).await.is_err());
+    assert!(store.is_using_failover() && clone.is_using_failover());
+
+    clone.create(&mut record).await.unwrap();
+    assert_loads(&store, &record).await;
+    assert_eq!(secondary.count_rows("session").await, 1);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn closed_primary_pool_fails_over_to_the_secondary() {
+    let primary = TestDb::migrated().await;
+    let secondary = TestDb::migrated().await;
+    let store = primary.store().with_failover(secondary.conn.clone(), Duration::ZERO);
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    assert!(!store.is_using_failover());
+
+    primary.conn.close_by_ref().await.unwrap();
+    assert!(store.load(&record.id).await.is_err());
+    assert!(store.is_using_failover());
+
+    // The session only exists on the primary
+    assert_eq!(store.load(&record.id).await.unwrap(), None);
+    store.create(&mut record).await.unwrap();
+    assert_loads(&store, &record).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn primary_stays_in_use_until_unreachable_for_the_failover_period() {
+    let secondary = TestDb::migrated().await;
+    let store = PostgresStore::new(unreachable_connection().await)
+        .with_failover(secondary.conn.clone(), Duration::from_secs(3600));
+
+    let mut record = active_record();
+    assert!(store.create(&mut record).await.is_err());
+    assert!(store.create(&mut record).await.is_err());
+    assert!(!store.is_using_failover());
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn errors_from_a_reachable_primary_do_not_fail_over() {
+    let primary = TestDb::migrated().await;
+    let secondary = TestDb::migrated().await;
+    let store = primary
+        .store()
+        .with_table_name("missing_sessions")
+        .unwrap()
+        .with_failover(secondary.conn.clone(), Duration::ZERO);
+
+    assert!(store.load(&active_record().id).await.is_err());
+    assert!(!store.is_using_failover());
+}
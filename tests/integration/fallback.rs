@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use tower_sessions::session::Record;
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::FallbackDeserializer;
+
+use crate::helpers::{active_record, assert_loads, TestDb};
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn json_fallback_loads_legacy_sessions_and_save_reencodes() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_fallback_deserializer(FallbackDeserializer::Json);
+
+    let mut record = active_record();
+    let json = serde_json::to_vec(&record).unwrap();
+    store.upsert_raw(&record.id, json, record.expiry_date).await.unwrap();
+
+    assert_loads(&store, &record).await;
+
+    record.data.insert("theme".to_string(), "light".into());
+    store.save(&record).await.unwrap();
+
+    let data = db.stored_data(&record.id.to_string()).await;
+    assert_eq!(data[0], 0xC3, "session was not re-encoded as MessagePack");
+    let reencoded: Record = rmp_serde::from_slice(&data[1..]).unwrap();
+    assert_eq!(reencoded, record);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn custom_fallback_is_used_after_messagepack() {
+    let db = TestDb::migrated().await;
+    let legacy = active_record();
+    let decoded = legacy.clone();
+    let store = db.store().with_fallback_deserializer(FallbackDeserializer::Custom(Arc::new(
+        move |data| match data {
+            b"legacy" => Ok(decoded.clone()),
+            _ => Err("unknown format".to_string()),
+        },
+    )));
+
+    store.upsert_raw(&legacy.id, b"legacy".to_vec(), legacy.expiry_date).await.unwrap();
+    assert_loads(&store, &legacy).await;
+
+    // MessagePack data still takes precedence
+    let mut current = active_record();
+    store.create(&mut current).await.unwrap();
+    assert_loads(&store, &current).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn failing_fallback_applies_recovery_strategy() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_fallback_deserializer(FallbackDeserializer::Json);
+
+    let record = active_record();
+    store.upsert_raw(&record.id, b"garbage".to_vec(), record.expiry_date).await.unwrap();
+
+    assert!(matches!(
+        store.load(&record.id).await,
+        Err(tower_sessions::session_store::Error::Decode(_))
+    ));
+}
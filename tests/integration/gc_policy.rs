@@ -0,0 +1,79 @@
+use sea_orm::DatabaseConnection;
+use time::Duration;
+use tower_sessions_seaorm_store::{GarbageCollectionPolicy, PostgresStore};
+
+// The policy only reflects the configuration, so no database is needed
+fn disconnected_store() -> PostgresStore {
+    PostgresStore::new(DatabaseConnection::Disconnected)
+}
+
+#[test]
+fn default_policy_has_nothing_configured() {
+    let policy = disconnected_store().gc_policy();
+
+    assert_eq!(
+        policy,
+        GarbageCollectionPolicy {
+            delete_expired_on_start: false,
+            cleanup_interval: None,
+            batch_size: None,
+            idle_session_ttl: None,
+            soft_delete_retention: None,
+        }
+    );
+    assert_eq!(
+        serde_json::to_value(policy).unwrap(),
+        serde_json::json!({
+            "delete_expired_on_start": false,
+            "cleanup_interval": null,
+            "batch_size": null,
+            "idle_session_ttl": null,
+            "soft_delete_retention": null,
+        })
+    );
+}
+
+#[test]
+fn policy_reflects_idle_session_ttl() {
+    let policy = disconnected_store()
+        .with_idle_session_ttl(Duration::minutes(30))
+        .gc_policy();
+
+    assert_eq!(policy.idle_session_ttl, Some(Duration::minutes(30)));
+    assert_eq!(serde_json::to_value(policy).unwrap()["idle_session_ttl"], 1800);
+    assert!(policy.to_string().contains("idle session TTL: 30m"));
+}
+
+#[test]
+fn policy_reflects_soft_delete_retention_and_batch_size() {
+    let policy = disconnected_store()
+        .with_soft_delete(Duration::days(30))
+        .with_cleanup_batch_size(10_000)
+        .gc_policy();
+
+    assert_eq!(policy.soft_delete_retention, Some(Duration::days(30)));
+    assert_eq!(policy.batch_size, Some(10_000));
+    assert!(!policy.delete_expired_on_start);
+    assert_eq!(serde_json::to_value(policy).unwrap()["soft_delete_retention"], 2_592_000);
+    assert_eq!(serde_json::to_value(policy).unwrap()["batch_size"], 10_000);
+}
+
+#[test]
+fn policy_displays_every_setting() {
+    assert_eq!(
+        disconnected_store().gc_policy().to_string(),
+        "delete expired on start: no, cleanup interval: none, batch size: unlimited, idle session TTL: none, soft delete retention: none",
+    );
+
+    let policy = GarbageCollectionPolicy {
+        delete_expired_on_start: true,
+        cleanup_interval: Some(Duration::hours(1)),
+        batch_size: Some(500),
+        idle_session_ttl: Some(Duration::minutes(30)),
+        soft_delete_retention: Some(Duration::days(30)),
+    };
+    assert_eq!(
+        policy.to_string(),
+        "delete expired on start: yes, cleanup interval: 1h, batch size: 500, idle session TTL: 30m, soft delete retention: 30d",
+    );
+}
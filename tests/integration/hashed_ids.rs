@@ -0,0 +1,92 @@
+use tokio_stream::StreamExt;
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+use sha2::{Digest, Sha256};
+use tower_sessions::session::Id;
+use tower_sessions::{session_store, SessionStore};
+
+use crate::helpers::{active_record, assert_loads, assert_missing, TestDb};
+
+/// Returns the IDs of all rows of the default session table.
+async fn stored_ids(db: &TestDb) -> Vec<String> {
+    db.conn
+        .query_all(Statement::from_string(
+            DbBackend::Postgres,
+            r#"SELECT id FROM "tower_sessions"."session""#,
+        ))
+        .await
+        .unwrap()
+        .iter()
+        .map(|row| row.try_get("", "id").unwrap())
+        .collect()
+}
+
+fn sha256_hex(id: &Id) -> String {
+    format!("{:x}", Sha256::digest(id.to_string()))
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn rows_are_keyed_by_the_hash_of_the_session_id() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_hashed_session_ids(true);
+    let mut record = active_record();
+
+    store.create(&mut record).await.unwrap();
+    assert_eq!(stored_ids(&db).await, [sha256_hex(&record.id)]);
+    assert_loads(&store, &record).await;
+
+    // The ID isn't stored with the record either
+    let data = db.stored_data(&sha256_hex(&record.id)).await;
+    let stored: tower_sessions::session::Record = rmp_serde::from_slice(&data[1..]).unwrap();
+    assert_eq!(stored.id, Id(0));
+
+    // Nothing is found under the raw ID
+    assert_missing(&db.store(), &record.id).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn save_and_delete_hash_the_session_id() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_hashed_session_ids(true);
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    record.data.insert("theme".to_string(), "light".into());
+    store.save(&record).await.unwrap();
+    assert_loads(&store, &record).await;
+    assert_eq!(store.session_version(&record.id).await.unwrap(), Some(1));
+
+    store.delete(&record.id).await.unwrap();
+    assert!(stored_ids(&db).await.is_empty());
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn listing_session_ids_fails_when_they_are_hashed() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_hashed_session_ids(true);
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    assert!(matches!(
+        store.sessions_for_user("42", 0, 10).await,
+        Err(session_store::Error::Backend(message)) if message.contains("hashed")
+    ));
+
+    let results: Vec<_> = store.stream_active().collect().await;
+    assert!(matches!(results.as_slice(), [Err(session_store::Error::Backend(_))]));
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn load_many_finds_sessions_by_their_hashed_ids() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_hashed_session_ids(true);
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    let records = store.load_many(&[record.id, Id::default()]).await.unwrap();
+
+    assert_eq!(records, [Some(record), None]);
+}
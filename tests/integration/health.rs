@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use sea_orm::ConnectionTrait;
+use tower_sessions_seaorm_store::HealthReport;
+
+use crate::helpers::TestDb;
+
+#[test]
+fn report_serializes_latency_in_milliseconds() {
+    let report = HealthReport {
+        database_reachable: true,
+        ping_latency: Some(Duration::from_millis(12)),
+        table_exists: false,
+        missing_columns: Vec::new(),
+        error: None,
+    };
+
+    assert!(!report.is_healthy());
+    assert_eq!(
+        serde_json::to_value(&report).unwrap(),
+        serde_json::json!({
+            "database_reachable": true,
+            "ping_latency": 12,
+            "table_exists": false,
+            "missing_columns": [],
+            "error": null,
+        })
+    );
+    assert!(report.to_string().starts_with("unhealthy, database reachable: yes, ping latency: 12ms"));
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn migrated_store_is_healthy() {
+    let db = TestDb::migrated().await;
+
+    let report = db.store().health_check().await;
+
+    assert!(report.is_healthy(), "{report}");
+    assert!(report.ping_latency.is_some());
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn health_check_reports_missing_table() {
+    let db = TestDb::new().await;
+
+    let report = db.store().health_check().await;
+
+    assert!(report.database_reachable);
+    assert!(!report.table_exists);
+    assert!(!report.is_healthy());
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn health_check_reports_missing_columns() {
+    let db = TestDb::migrated().await;
+    db.conn
+        .execute_unprepared(r#"ALTER TABLE "tower_sessions"."session" DROP COLUMN "version""#)
+        .await
+        .unwrap();
+
+    let report = db.store().health_check().await;
+
+    assert!(report.table_exists);
+    assert_eq!(report.missing_columns, ["version"]);
+    assert!(!report.is_healthy());
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn closed_store_is_unhealthy() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    store.close().await.unwrap();
+
+    let report = store.health_check().await;
+
+    assert!(!report.database_reachable);
+    assert_eq!(report.error.as_deref(), Some("store is closed"));
+}
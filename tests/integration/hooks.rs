@@ -0,0 +1,148 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use tower_sessions::session::{Id, Record};
+use tower_sessions::{ExpiredDeletion, SessionStore};
+use tower_sessions_seaorm_store::{ConditionalSaveExpr, SessionHooks};
+
+use crate::helpers::{active_record, expired_record, TestDb};
+
+#[derive(Debug, PartialEq, Eq)]
+enum HookCall {
+    Create(Id),
+    Save(Id),
+    LoadMiss(Id),
+    Delete(Id),
+    ExpiredCleanup(u64),
+}
+
+/// Records every hook call it receives.
+#[derive(Default)]
+struct RecordingHooks {
+    calls: Mutex<Vec<HookCall>>,
+}
+
+impl RecordingHooks {
+    fn take_calls(&self) -> Vec<HookCall> {
+        std::mem::take(&mut *self.calls.lock().unwrap())
+    }
+}
+
+#[async_trait]
+impl SessionHooks for RecordingHooks {
+    async fn on_create(&self, record: &Record) {
+        self.calls.lock().unwrap().push(HookCall::Create(record.id));
+    }
+
+    async fn on_save(&self, record: &Record) {
+        self.calls.lock().unwrap().push(HookCall::Save(record.id));
+    }
+
+    async fn on_load_miss(&self, id: &Id) {
+        self.calls.lock().unwrap().push(HookCall::LoadMiss(*id));
+    }
+
+    async fn on_delete(&self, id: &Id) {
+        self.calls.lock().unwrap().push(HookCall::Delete(*id));
+    }
+
+    async fn on_expired_cleanup(&self, deleted: u64) {
+        self.calls.lock().unwrap().push(HookCall::ExpiredCleanup(deleted));
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn copied_sessions_are_reported_as_created() {
+    let db = TestDb::migrated().await;
+    let hooks = Arc::new(RecordingHooks::default());
+    let store = db.store().with_hooks(hooks.clone());
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    hooks.take_calls();
+
+    let copy_id = Id::default();
+    store.copy_session(&record.id, &copy_id).await.unwrap();
+    assert_eq!(hooks.take_calls(), [HookCall::Create(copy_id)]);
+
+    // Failed copies aren't reported
+    assert!(store.copy_session(&record.id, &copy_id).await.is_err());
+    assert_eq!(hooks.take_calls(), []);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn conditional_saves_are_reported_once_written() {
+    let db = TestDb::migrated().await;
+    let hooks = Arc::new(RecordingHooks::default());
+    let store = db.store().with_hooks(hooks.clone());
+    let record = active_record();
+
+    assert!(!store.conditional_save(&record, ConditionalSaveExpr::OnlyIfExists).await.unwrap());
+    assert_eq!(hooks.take_calls(), []);
+
+    assert!(store.conditional_save(&record, ConditionalSaveExpr::OnlyIfNotExists).await.unwrap());
+    assert_eq!(hooks.take_calls(), [HookCall::Save(record.id)]);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_many_only_reports_deleted_sessions() {
+    let db = TestDb::migrated().await;
+    let hooks = Arc::new(RecordingHooks::default());
+    let store = db.store().with_hooks(hooks.clone());
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    hooks.take_calls();
+
+    store.delete_many(&[Id::default(), record.id]).await.unwrap();
+
+    assert_eq!(hooks.take_calls(), [HookCall::Delete(record.id)]);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn every_hook_is_awaited_after_its_operation() {
+    let db = TestDb::migrated().await;
+    let hooks = Arc::new(RecordingHooks::default());
+    let store = db.store().with_hooks(hooks.clone());
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    assert_eq!(hooks.take_calls(), [HookCall::Create(record.id)]);
+
+    store.save(&record).await.unwrap();
+    assert_eq!(hooks.take_calls(), [HookCall::Save(record.id)]);
+
+    // Only misses are reported
+    store.load(&record.id).await.unwrap();
+    assert_eq!(hooks.take_calls(), []);
+
+    store.delete(&record.id).await.unwrap();
+    assert_eq!(hooks.take_calls(), [HookCall::Delete(record.id)]);
+
+    store.load(&record.id).await.unwrap();
+    assert_eq!(hooks.take_calls(), [HookCall::LoadMiss(record.id)]);
+
+    let mut expired = expired_record();
+    store.create(&mut expired).await.unwrap();
+    hooks.take_calls();
+    store.delete_expired().await.unwrap();
+    assert_eq!(hooks.take_calls(), [HookCall::ExpiredCleanup(1)]);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn hooks_are_not_called_for_failed_operations() {
+    let db = TestDb::new().await;
+    let hooks = Arc::new(RecordingHooks::default());
+    let store = db.store().with_hooks(hooks.clone());
+
+    // The session table doesn't exist
+    let mut record = active_record();
+    assert!(store.create(&mut record).await.is_err());
+    assert!(store.load(&record.id).await.is_err());
+    assert!(store.delete(&record.id).await.is_err());
+
+    assert_eq!(hooks.take_calls(), []);
+}
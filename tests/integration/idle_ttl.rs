@@ -0,0 +1,37 @@
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+use time::{Duration, OffsetDateTime};
+use tower_sessions::session::Id;
+use tower_sessions::{ExpiredDeletion, SessionStore};
+
+use crate::helpers::{assert_loads, assert_missing, record, TestDb};
+
+/// Moves the last write of session `id` back by `idle`.
+async fn set_idle(db: &TestDb, id: &Id, idle: Duration) {
+    db.conn
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"UPDATE "tower_sessions"."session" SET last_accessed_at = $1 WHERE id = $2"#,
+            [(OffsetDateTime::now_utc() - idle).into(), id.to_string().into()],
+        ))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_expired_removes_idle_sessions_before_their_expiry_date() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_idle_session_ttl(Duration::minutes(30));
+    let expiry_date = OffsetDateTime::now_utc() + Duration::days(1);
+    let mut idle = record(expiry_date);
+    store.create(&mut idle).await.unwrap();
+    let mut recent = record(expiry_date);
+    store.create(&mut recent).await.unwrap();
+    set_idle(&db, &idle.id, Duration::hours(1)).await;
+
+    store.delete_expired().await.unwrap();
+
+    assert_eq!(db.count_rows("session").await, 1);
+    assert_missing(&store, &idle.id).await;
+    assert_loads(&store, &recent).await;
+}
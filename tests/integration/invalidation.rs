@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use sea_orm::TransactionTrait;
+use tokio_stream::StreamExt;
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::SeaOrmStoreError;
+
+use crate::helpers::{active_record, TestDb};
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_is_broadcast_to_subscribers() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_invalidation_channel("session_invalidations");
+    let mut invalidations = store.invalidation_stream().await.unwrap();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    store.delete(&record.id).await.unwrap();
+
+    let invalidated = tokio::time::timeout(Duration::from_secs(5), invalidations.next())
+        .await
+        .expect("no invalidation received")
+        .unwrap()
+        .unwrap();
+    assert_eq!(invalidated, record.id);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_many_broadcasts_every_session() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_invalidation_channel("session_invalidations");
+    let mut invalidations = store.invalidation_stream().await.unwrap();
+
+    let mut first = active_record();
+    let mut second = active_record();
+    store.create(&mut first).await.unwrap();
+    store.create(&mut second).await.unwrap();
+    store.delete_many(&[first.id, second.id]).await.unwrap();
+
+    let mut invalidated = Vec::new();
+    for _ in 0..2 {
+        let session_id = tokio::time::timeout(Duration::from_secs(5), invalidations.next())
+            .await
+            .expect("no invalidation received")
+            .unwrap()
+            .unwrap();
+        invalidated.push(session_id);
+    }
+    assert_eq!(invalidated, [first.id, second.id]);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_in_is_broadcast_on_commit() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_invalidation_channel("session_invalidations");
+    let mut invalidations = store.invalidation_stream().await.unwrap();
+
+    let mut rolled_back = active_record();
+    let mut committed = active_record();
+    store.create(&mut rolled_back).await.unwrap();
+    store.create(&mut committed).await.unwrap();
+
+    let txn = db.conn.begin().await.unwrap();
+    store.delete_in(&txn, &rolled_back.id).await.unwrap();
+    txn.rollback().await.unwrap();
+
+    let txn = db.conn.begin().await.unwrap();
+    store.delete_in(&txn, &committed.id).await.unwrap();
+    txn.commit().await.unwrap();
+
+    // The notification of the rolled back deletion is never sent
+    let invalidated = tokio::time::timeout(Duration::from_secs(5), invalidations.next())
+        .await
+        .expect("no invalidation received")
+        .unwrap()
+        .unwrap();
+    assert_eq!(invalidated, committed.id);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn invalidation_stream_requires_a_channel() {
+    let db = TestDb::migrated().await;
+
+    let err = db.store().invalidation_stream().await.err().unwrap();
+    assert!(matches!(err, SeaOrmStoreError::NoInvalidationChannel));
+}
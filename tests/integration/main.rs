@@ -0,0 +1,89 @@
+//! Integration tests running the store against a real PostgreSQL database.
+//!
+//! Tests that touch the database are ignored by default because they need either a running
+//! Docker daemon, or a PostgreSQL server whose URL is set in `DATABASE_URL`. Run them with:
+//!
+//! ```bash
+//! cargo test --features migration -- --include-ignored
+//! ```
+
+#[path = "../helpers.rs"]
+mod helpers;
+
+mod activity;
+#[cfg(feature = "pg-only")]
+mod advisory_lock;
+#[cfg(feature = "audit-log")]
+mod audit;
+mod backend;
+mod batch;
+#[cfg(feature = "benchmark")]
+mod benchmark;
+mod budget;
+mod bulk_import;
+#[cfg(feature = "moka")]
+mod cache;
+mod circuit_breaker;
+mod cleanup;
+#[cfg(feature = "cli")]
+mod cli;
+#[cfg(feature = "client-metadata")]
+mod client_metadata;
+mod close;
+#[cfg(feature = "compression")]
+mod compression;
+mod conditional;
+mod connection;
+mod copy;
+mod data_format;
+#[cfg(feature = "encryption")]
+mod encryption;
+mod envelope;
+mod equality;
+mod eviction;
+mod errors;
+mod export;
+mod failover;
+mod fallback;
+mod gc_policy;
+#[cfg(feature = "hashed-ids")]
+mod hashed_ids;
+mod health;
+mod hooks;
+mod idle_ttl;
+mod invalidation;
+mod max_lifetime;
+mod migration;
+#[cfg(feature = "mysql")]
+mod mysql;
+mod partition;
+mod purge;
+#[cfg(feature = "raw-query")]
+mod raw_query;
+mod read_only;
+mod recovery;
+mod redaction;
+mod reencode;
+mod replica;
+mod restore;
+mod retry;
+mod soft_delete;
+mod sqlx_store;
+mod startup;
+mod stats;
+mod store;
+mod stream;
+mod table_name;
+mod tables;
+mod telemetry;
+mod tenant;
+mod tenant_factory;
+mod timeout;
+mod timestamps;
+mod touch;
+mod transaction;
+mod ttl;
+mod unchanged_saves;
+mod user_id;
+mod validation;
+mod watch_count;
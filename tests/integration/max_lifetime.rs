@@ -0,0 +1,88 @@
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+use time::{Duration, OffsetDateTime};
+use tower_sessions::session::{Id, Record};
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::PostgresStore;
+
+use crate::helpers::{assert_missing, record, TestDb};
+
+/// Moves the creation time of session `id` back by `age`, or clears it without `age`.
+async fn set_age(db: &TestDb, id: &Id, age: Option<Duration>) {
+    let created_at = age.map(|age| OffsetDateTime::now_utc() - age);
+    db.conn
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"UPDATE "tower_sessions"."session" SET created_at = $1 WHERE id = $2"#,
+            [created_at.into(), id.to_string().into()],
+        ))
+        .await
+        .unwrap();
+}
+
+/// Returns the expiry date of session `record` as loaded by `store`.
+async fn loaded_expiry_date(store: &PostgresStore, record: &Record) -> OffsetDateTime {
+    store.load(&record.id).await.unwrap().expect("session not found").expiry_date
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn create_caps_the_expiry_date_at_the_max_lifetime() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_max_lifetime(Duration::days(1));
+    let mut record = record(OffsetDateTime::now_utc() + Duration::days(7));
+
+    store.create(&mut record).await.unwrap();
+
+    let expiry_date = loaded_expiry_date(&store, &record).await;
+    assert!(expiry_date <= OffsetDateTime::now_utc() + Duration::days(1), "{expiry_date}");
+    assert!(expiry_date > OffsetDateTime::now_utc() + Duration::hours(23), "{expiry_date}");
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn saves_cannot_extend_sessions_past_their_max_lifetime() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_max_lifetime(Duration::days(1));
+    let mut record = record(OffsetDateTime::now_utc() + Duration::hours(1));
+    store.create(&mut record).await.unwrap();
+    set_age(&db, &record.id, Some(Duration::hours(23))).await;
+
+    // Sliding the expiry date a week ahead
+    record.expiry_date = OffsetDateTime::now_utc() + Duration::days(7);
+    store.save(&record).await.unwrap();
+
+    // The session ends a day after its creation, an hour from now
+    let expiry_date = loaded_expiry_date(&store, &record).await;
+    let deadline = OffsetDateTime::now_utc() + Duration::hours(1);
+    assert!((expiry_date - deadline).abs() < Duration::minutes(1), "{expiry_date}");
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn sessions_past_their_max_lifetime_expire_on_save() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_max_lifetime(Duration::days(1));
+    let mut record = record(OffsetDateTime::now_utc() + Duration::hours(1));
+    store.create(&mut record).await.unwrap();
+    set_age(&db, &record.id, Some(Duration::days(2))).await;
+
+    record.expiry_date = OffsetDateTime::now_utc() + Duration::days(7);
+    store.save(&record).await.unwrap();
+
+    assert_missing(&store, &record.id).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn sessions_without_a_creation_time_are_not_capped() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_max_lifetime(Duration::days(1));
+    let mut record = record(OffsetDateTime::now_utc() + Duration::hours(1));
+    store.create(&mut record).await.unwrap();
+    set_age(&db, &record.id, None).await;
+
+    record.expiry_date = OffsetDateTime::now_utc() + Duration::days(7);
+    store.save(&record).await.unwrap();
+
+    assert!(loaded_expiry_date(&store, &record).await > OffsetDateTime::now_utc() + Duration::days(6));
+}
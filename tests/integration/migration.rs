@@ -0,0 +1,371 @@
+use sea_orm::{ConnectionTrait, DbBackend, FromQueryResult, Statement};
+use tower_sessions_seaorm_store::migration::*;
+
+use crate::helpers::TestDb;
+
+#[derive(Debug, FromQueryResult)]
+struct ColumnInfo {
+    column_name: String,
+    data_type: String,
+    is_nullable: String,
+}
+
+/// Returns the columns of the session table once every built-in migration has run.
+fn session_columns() -> Vec<&'static str> {
+    #[allow(unused_mut)]
+    let mut columns = vec![
+        "id",
+        "data",
+        "expiry_date",
+        "last_accessed_at",
+        "version",
+        "user_id",
+        "created_at",
+        "updated_at",
+    ];
+    #[cfg(feature = "client-metadata")]
+    columns.extend(["ip_address", "user_agent"]);
+    columns.extend(["tenant_id", "deleted_at", "data_hash"]);
+    columns
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn migrate_creates_session_table() {
+    let db = TestDb::migrated().await;
+
+    let columns = ColumnInfo::find_by_statement(Statement::from_string(
+        DbBackend::Postgres,
+        "SELECT column_name, data_type, is_nullable FROM information_schema.columns \
+         WHERE table_schema = 'tower_sessions' AND table_name = 'session' \
+         ORDER BY ordinal_position",
+    ))
+    .all(&db.conn)
+    .await
+    .unwrap();
+
+    let columns: Vec<_> = columns
+        .iter()
+        .map(|column| {
+            (
+                column.column_name.as_str(),
+                column.data_type.as_str(),
+                column.is_nullable.as_str(),
+            )
+        })
+        .collect();
+
+    #[allow(unused_mut)]
+    let mut expected = vec![
+        ("id", "text", "NO"),
+        ("data", "bytea", "NO"),
+        ("expiry_date", "timestamp with time zone", "NO"),
+        ("last_accessed_at", "timestamp with time zone", "NO"),
+        ("version", "bigint", "NO"),
+        ("user_id", "text", "YES"),
+        ("created_at", "timestamp with time zone", "YES"),
+        ("updated_at", "timestamp with time zone", "YES"),
+    ];
+    #[cfg(feature = "client-metadata")]
+    expected.extend([("ip_address", "text", "YES"), ("user_agent", "text", "YES")]);
+    expected.push(("tenant_id", "text", "YES"));
+    expected.push(("deleted_at", "timestamp with time zone", "YES"));
+    expected.push(("data_hash", "bigint", "YES"));
+    assert_eq!(columns, expected);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn migrate_is_idempotent() {
+    let db = TestDb::migrated().await;
+
+    db.store().migrate().await.unwrap();
+}
+
+#[cfg(feature = "pg-only")]
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_expired_uses_expiry_date_index() {
+    use tower_sessions::session::Id;
+    use tower_sessions_seaorm_store::StoreOperation;
+
+    let db = TestDb::migrated().await;
+    // With only a few expired sessions among many, a sequential scan would be the slow path
+    db.conn
+        .execute_unprepared(
+            r#"INSERT INTO "tower_sessions"."session" ("id", "data", "expiry_date")
+               SELECT g::text, ''::bytea, now() + interval '1 day' FROM generate_series(1, 10000) g;
+               ANALYZE "tower_sessions"."session";"#,
+        )
+        .await
+        .unwrap();
+
+    let plan = db
+        .store()
+        .explain_plan(StoreOperation::DeleteExpired, &Id::default())
+        .await
+        .unwrap();
+
+    assert!(plan.is_index_scan(), "{plan:?}");
+}
+
+/// Adds a `region` column to the session table; fails if it runs twice.
+struct AddRegion;
+
+impl MigrationName for AddRegion {
+    fn name(&self) -> &str {
+        "m20261017_000001_add_region"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for AddRegion {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Alias::new("tower_sessions"), Alias::new("session")))
+                    .add_column(ColumnDef::new(Alias::new("region")).text())
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Indexes the `region` column, so it has to run after `AddRegion`.
+struct IndexRegion;
+
+impl MigrationName for IndexRegion {
+    fn name(&self) -> &str {
+        "m20261017_000002_index_region"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for IndexRegion {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .name("session_region_idx")
+                    .table((Alias::new("tower_sessions"), Alias::new("session")))
+                    .col(Alias::new("region"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+async fn column_names(db: &TestDb) -> Vec<String> {
+    ColumnInfo::find_by_statement(Statement::from_string(
+        DbBackend::Postgres,
+        "SELECT column_name, data_type, is_nullable FROM information_schema.columns \
+         WHERE table_schema = 'tower_sessions' AND table_name = 'session' \
+         ORDER BY ordinal_position",
+    ))
+    .all(&db.conn)
+    .await
+    .unwrap()
+    .into_iter()
+    .map(|column| column.column_name)
+    .collect()
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn custom_migration_runs_after_built_in_migrations() {
+    let db = TestDb::new().await;
+    let store = db.store().with_custom_migration(Box::new(AddRegion));
+
+    store.migrate().await.unwrap();
+    assert_eq!(column_names(&db).await, [session_columns(), vec!["region"]].concat());
+
+    // Applied migrations are skipped
+    store.migrate().await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn custom_migrations_run_in_order() {
+    let db = TestDb::migrated().await;
+    let store = db
+        .store()
+        .with_custom_migrations(vec![Box::new(AddRegion), Box::new(IndexRegion)]);
+
+    store.migrate().await.unwrap();
+    assert!(column_names(&db).await.contains(&"region".to_string()));
+    let index = db
+        .conn
+        .query_one(Statement::from_string(
+            DbBackend::Postgres,
+            "SELECT indexname FROM pg_indexes \
+             WHERE schemaname = 'tower_sessions' AND indexname = 'session_region_idx'",
+        ))
+        .await
+        .unwrap();
+    assert!(index.is_some());
+}
+
+/// Adds a `region` column to the session table, and drops it again when rolled back.
+struct RevertibleRegion;
+
+impl MigrationName for RevertibleRegion {
+    fn name(&self) -> &str {
+        "m20261017_000003_revertible_region"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for RevertibleRegion {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        AddRegion.up(manager).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Alias::new("tower_sessions"), Alias::new("session")))
+                    .drop_column(Alias::new("region"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn migrate_down_rolls_back_every_migration() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    store.migrate_down(None).await.unwrap();
+    assert!(column_names(&db).await.is_empty());
+
+    // The schema can be migrated again afterwards
+    store.migrate().await.unwrap();
+    assert_eq!(column_names(&db).await, session_columns());
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn migrate_down_rolls_back_custom_migrations_first() {
+    let db = TestDb::new().await;
+    let store = db.store().with_custom_migration(Box::new(RevertibleRegion));
+    store.migrate().await.unwrap();
+
+    store.migrate_down(Some(1)).await.unwrap();
+    assert_eq!(column_names(&db).await, session_columns());
+
+    // The last built-in migration adds the data hash column
+    store.migrate_down(Some(1)).await.unwrap();
+    let columns = session_columns();
+    assert_eq!(column_names(&db).await, columns[..columns.len() - 1]);
+
+    // Rolled back migrations are applied again
+    store.migrate().await.unwrap();
+    assert!(column_names(&db).await.contains(&"region".to_string()));
+}
+
+#[test]
+fn generate_sql_renders_the_configured_table() {
+    let sql = generate_sql(DbBackend::Postgres, "prod_sessions", "myapp");
+
+    assert!(sql.starts_with(r#"CREATE SCHEMA IF NOT EXISTS "myapp";"#), "{sql}");
+    assert!(sql.contains(r#"CREATE TABLE IF NOT EXISTS "myapp"."prod_sessions""#), "{sql}");
+    assert!(sql.contains(r#""idx-prod_sessions-expiry_date" ON "myapp"."prod_sessions""#), "{sql}");
+    assert!(sql.contains(r#"ADD COLUMN IF NOT EXISTS "user_id""#), "{sql}");
+    assert!(!sql.contains("tower_sessions"), "{sql}");
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn generated_sql_creates_a_usable_schema() {
+    use tower_sessions::SessionStore;
+
+    let db = TestDb::new().await;
+    db.conn
+        .execute_unprepared(&generate_sql(DbBackend::Postgres, "session", "tower_sessions"))
+        .await
+        .unwrap();
+
+    let store = db.store();
+    let mut record = crate::helpers::active_record();
+    store.create(&mut record).await.unwrap();
+    crate::helpers::assert_loads(&store, &record).await;
+
+    // The store can still be migrated once the SQL has been applied
+    store.migrate().await.unwrap();
+    assert_eq!(column_names(&db).await, session_columns());
+}
+
+/// Returns the persistence of the default session table: `p` for logged, `u` for unlogged.
+async fn table_persistence(db: &TestDb) -> String {
+    db.conn
+        .query_one(Statement::from_string(
+            DbBackend::Postgres,
+            "SELECT relpersistence::text AS persistence FROM pg_class \
+             WHERE oid = 'tower_sessions.session'::regclass",
+        ))
+        .await
+        .unwrap()
+        .unwrap()
+        .try_get("", "persistence")
+        .unwrap()
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn migrate_creates_unlogged_table_when_configured() {
+    use tower_sessions::SessionStore;
+
+    let db = TestDb::new().await;
+    let store = db.store().with_migration_config(MigrationConfig {
+        unlogged: true,
+        ..Default::default()
+    });
+    store.migrate().await.unwrap();
+    assert_eq!(table_persistence(&db).await, "u");
+
+    let mut record = crate::helpers::active_record();
+    store.create(&mut record).await.unwrap();
+    crate::helpers::assert_loads(&store, &record).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn migrate_creates_logged_table_by_default() {
+    let db = TestDb::migrated().await;
+
+    assert_eq!(table_persistence(&db).await, "p");
+}
+
+#[test]
+fn generate_sql_ignores_unlogged_on_other_backends() {
+    let config = MigrationConfig {
+        unlogged: true,
+        ..Default::default()
+    };
+
+    let sql = generate_sql_with_config(DbBackend::Sqlite, "session", "tower_sessions", &config);
+    assert!(sql.starts_with("CREATE TABLE"), "{sql}");
+    let sql = generate_sql_with_config(DbBackend::Postgres, "session", "tower_sessions", &config);
+    assert!(sql.contains("CREATE UNLOGGED TABLE"), "{sql}");
+}
+
+#[test]
+fn generate_sql_partitions_by_expiry_date_when_configured() {
+    let config = MigrationConfig {
+        partition_interval: Some(PartitionInterval::Daily),
+        ..Default::default()
+    };
+
+    let sql = generate_sql_with_config(DbBackend::Postgres, "session", "tower_sessions", &config);
+    assert!(sql.contains(r#"PRIMARY KEY ("id", "expiry_date") ) PARTITION BY RANGE ("expiry_date")"#), "{sql}");
+    assert!(
+        sql.contains(r#"CREATE TABLE IF NOT EXISTS "tower_sessions"."session_default" PARTITION OF "tower_sessions"."session" DEFAULT"#),
+        "{sql}"
+    );
+    let sql = generate_sql_with_config(DbBackend::Sqlite, "session", "tower_sessions", &config);
+    assert!(!sql.contains("PARTITION"), "{sql}");
+}
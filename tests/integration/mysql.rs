@@ -0,0 +1,199 @@
+//! Tests against a MySQL server, whose URL must be set in `MYSQL_DATABASE_URL`.
+//!
+//! The tests pass without running anything when the variable isn't set.
+
+use sea_orm::{ConnectionTrait, Database, DatabaseConnection};
+use tower_sessions::{ExpiredDeletion, SessionStore};
+use tower_sessions_seaorm_store::{user_id_key, ConditionalSaveExpr, EvictionPolicy, SeaOrmStore};
+
+use crate::helpers::{active_record, assert_loads, assert_missing, expired_record};
+
+/// Connects to a new, empty database on the server of `MYSQL_DATABASE_URL`, if set.
+async fn mysql_database() -> Option<DatabaseConnection> {
+    let Ok(url) = std::env::var("MYSQL_DATABASE_URL") else {
+        eprintln!("MYSQL_DATABASE_URL is not set, skipping");
+        return None;
+    };
+
+    let database_name = format!("tower_sessions_test_{}", unique_suffix());
+    let admin = Database::connect(&url).await.expect("failed to connect to MYSQL_DATABASE_URL");
+    admin
+        .execute_unprepared(&format!("CREATE DATABASE `{database_name}`"))
+        .await
+        .expect("failed to create the test database");
+    admin.close().await.expect("failed to close the admin connection");
+
+    let (server_url, _) = url.rsplit_once('/').expect("MYSQL_DATABASE_URL must name a database");
+    let conn = Database::connect(format!("{server_url}/{database_name}"))
+        .await
+        .expect("failed to connect to the test database");
+    Some(conn)
+}
+
+/// Returns a suffix that is unique per test and per run.
+fn unique_suffix() -> String {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static DATABASE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    format!(
+        "{}_{}_{}",
+        std::process::id(),
+        time::OffsetDateTime::now_utc().unix_timestamp(),
+        DATABASE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+#[tokio::test]
+#[ignore = "requires MYSQL_DATABASE_URL"]
+async fn store_works_over_mysql() {
+    let Some(conn) = mysql_database().await else {
+        return;
+    };
+    let store = SeaOrmStore::new(conn);
+    store.migrate().await.unwrap();
+    // Running the migrations again is a no-op
+    store.migrate().await.unwrap();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    assert_loads(&store, &record).await;
+
+    record.data.insert("theme".to_string(), "light".into());
+    store.save(&record).await.unwrap();
+    assert_loads(&store, &record).await;
+    assert_eq!(store.session_version(&record.id).await.unwrap(), Some(1));
+
+    let mut changed = record.clone();
+    changed.data.insert("theme".to_string(), "dark".into());
+    assert!(!store
+        .conditional_save(&changed, ConditionalSaveExpr::OnlyIfNotExists)
+        .await
+        .unwrap());
+    assert_loads(&store, &record).await;
+
+    store.delete(&record.id).await.unwrap();
+    assert_missing(&store, &record.id).await;
+}
+
+#[tokio::test]
+#[ignore = "requires MYSQL_DATABASE_URL"]
+async fn mysql_expired_sessions_are_deleted() {
+    let Some(conn) = mysql_database().await else {
+        return;
+    };
+    let store = SeaOrmStore::new(conn);
+    store.migrate().await.unwrap();
+
+    let mut active = active_record();
+    let mut expired = expired_record();
+    store.create(&mut active).await.unwrap();
+    store.create(&mut expired).await.unwrap();
+
+    assert_missing(&store, &expired.id).await;
+    store.delete_expired().await.unwrap();
+    assert_eq!(store.count_active().await.unwrap(), 1);
+}
+
+#[tokio::test]
+#[ignore = "requires MYSQL_DATABASE_URL"]
+async fn mysql_expired_sessions_are_deleted_in_batches() {
+    let Some(conn) = mysql_database().await else {
+        return;
+    };
+    let store = SeaOrmStore::new(conn);
+    store.migrate().await.unwrap();
+
+    let mut active = active_record();
+    store.create(&mut active).await.unwrap();
+    for _ in 0..5 {
+        store.create(&mut expired_record()).await.unwrap();
+    }
+
+    assert_eq!(store.delete_expired_batched(2).await.unwrap(), 5);
+    assert_eq!(store.count_active().await.unwrap(), 1);
+}
+
+#[tokio::test]
+#[ignore = "requires MYSQL_DATABASE_URL"]
+async fn mysql_stats_count_sessions_and_payload_bytes() {
+    let Some(conn) = mysql_database().await else {
+        return;
+    };
+    let store = SeaOrmStore::new(conn);
+    store.migrate().await.unwrap();
+    store.create(&mut active_record()).await.unwrap();
+    store.create(&mut expired_record()).await.unwrap();
+
+    let stats = store.stats().await.unwrap();
+
+    assert_eq!((stats.total_sessions, stats.active_sessions, stats.expired_sessions), (2, 1, 1));
+    assert!(stats.total_payload_bytes > 0);
+}
+
+#[tokio::test]
+#[ignore = "requires MYSQL_DATABASE_URL"]
+async fn mysql_evicts_sessions_beyond_the_limit_per_user() {
+    let Some(conn) = mysql_database().await else {
+        return;
+    };
+    let store = SeaOrmStore::new(conn)
+        .with_user_id_extractor(user_id_key("user_id"))
+        .with_max_sessions_per_user(1, EvictionPolicy::OldestFirst);
+    store.migrate().await.unwrap();
+
+    // Both records hold `"user_id": 42`
+    let mut first = active_record();
+    let mut second = active_record();
+    store.create(&mut first).await.unwrap();
+    store.create(&mut second).await.unwrap();
+
+    assert_missing(&store, &first.id).await;
+    assert_loads(&store, &second).await;
+}
+
+#[tokio::test]
+#[ignore = "requires MYSQL_DATABASE_URL"]
+async fn mysql_saves_cannot_extend_sessions_past_their_max_lifetime() {
+    use time::{Duration, OffsetDateTime};
+
+    let Some(conn) = mysql_database().await else {
+        return;
+    };
+    let store = SeaOrmStore::new(conn.clone()).with_max_lifetime(Duration::days(1));
+    store.migrate().await.unwrap();
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    // Created 23 hours ago
+    conn.execute_unprepared("UPDATE session SET created_at = UTC_TIMESTAMP(6) - INTERVAL 23 HOUR")
+        .await
+        .unwrap();
+
+    record.expiry_date = OffsetDateTime::now_utc() + Duration::days(7);
+    store.save(&record).await.unwrap();
+
+    let expiry_date = store.load(&record.id).await.unwrap().unwrap().expiry_date;
+    let deadline = OffsetDateTime::now_utc() + Duration::hours(1);
+    assert!((expiry_date - deadline).abs() < Duration::minutes(1), "{expiry_date}");
+}
+
+#[tokio::test]
+#[ignore = "requires MYSQL_DATABASE_URL"]
+async fn mysql_saves_leave_sessions_of_other_tenants_untouched() {
+    let Some(conn) = mysql_database().await else {
+        return;
+    };
+    let store = SeaOrmStore::new(conn);
+    store.migrate().await.unwrap();
+    let acme = store.for_tenant("acme");
+    let globex = store.for_tenant("globex");
+    let mut record = active_record();
+    acme.create(&mut record).await.unwrap();
+
+    let mut hijacked = record.clone();
+    hijacked.data.insert("theme".to_string(), "hijacked".into());
+    globex.save(&hijacked).await.unwrap();
+
+    assert_loads(&acme, &record).await;
+    assert_missing(&globex, &record.id).await;
+    assert_eq!(acme.session_version(&record.id).await.unwrap(), Some(0));
+}
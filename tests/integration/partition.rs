@@ -0,0 +1,131 @@
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+use time::{Duration, OffsetDateTime};
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::migration::{MigrationConfig, PartitionInterval};
+use tower_sessions_seaorm_store::PostgresStore;
+
+use crate::helpers::{active_record, assert_loads, record, TestDb};
+
+/// Returns a store on a session table partitioned by day, migrated in `db`.
+async fn partitioned_store(db: &TestDb) -> PostgresStore {
+    let store = db.store().with_migration_config(MigrationConfig {
+        partition_interval: Some(PartitionInterval::Daily),
+        ..Default::default()
+    });
+    store.migrate().await.unwrap();
+    store
+}
+
+/// Returns the names of the partitions of the default session table, in alphabetical order.
+async fn partitions(db: &TestDb) -> Vec<String> {
+    db.conn
+        .query_all(Statement::from_string(
+            DbBackend::Postgres,
+            "SELECT c.relname::text AS partition FROM pg_inherits i \
+             JOIN pg_class c ON c.oid = i.inhrelid \
+             WHERE i.inhparent = 'tower_sessions.session'::regclass ORDER BY c.relname",
+        ))
+        .await
+        .unwrap()
+        .iter()
+        .map(|row| row.try_get("", "partition").unwrap())
+        .collect()
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn save_keeps_one_row_when_expiry_date_changes() {
+    let db = TestDb::new().await;
+    let store = partitioned_store(&db).await;
+    store.create_partitions(3).await.unwrap();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    for days in 2..4 {
+        record.expiry_date = OffsetDateTime::now_utc() + Duration::days(days);
+        store.save(&record).await.unwrap();
+    }
+    store.save(&record).await.unwrap();
+
+    assert_eq!(db.count_rows("session").await, 1);
+    assert_eq!(store.session_version(&record.id).await.unwrap(), Some(3));
+    assert_loads(&store, &record).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn create_partitions_moves_sessions_out_of_default_partition() {
+    let db = TestDb::new().await;
+    let store = partitioned_store(&db).await;
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    assert_eq!(db.count_rows("session_default").await, 1);
+
+    assert_eq!(store.create_partitions(2).await.unwrap(), 3);
+    assert_eq!(store.create_partitions(2).await.unwrap(), 0);
+
+    assert_eq!(partitions(&db).await.len(), 4);
+    assert_eq!(db.count_rows("session_default").await, 0);
+    assert_loads(&store, &record).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn drop_expired_partitions_keeps_active_sessions() {
+    let db = TestDb::new().await;
+    let store = partitioned_store(&db).await;
+    db.conn
+        .execute_unprepared(
+            "CREATE TABLE tower_sessions.session_p20000101 PARTITION OF tower_sessions.session \
+             FOR VALUES FROM ('2000-01-01T00:00:00Z') TO ('2000-01-02T00:00:00Z')",
+        )
+        .await
+        .unwrap();
+    store.create_partitions(1).await.unwrap();
+    let expired = OffsetDateTime::from_unix_timestamp(946_728_000).unwrap();
+    store.create(&mut record(expired)).await.unwrap();
+    let mut active = active_record();
+    store.create(&mut active).await.unwrap();
+
+    assert_eq!(store.drop_expired_partitions().await.unwrap(), 1);
+
+    assert!(!partitions(&db).await.contains(&"session_p20000101".to_string()));
+    assert_eq!(db.count_rows("session").await, 1);
+    assert_loads(&store, &active).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn list_tables_leaves_out_partitions() {
+    let db = TestDb::new().await;
+    let store = partitioned_store(&db).await;
+    store.create_partitions(1).await.unwrap();
+
+    assert_eq!(store.list_tables().await.unwrap(), ["session"]);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn partition_maintenance_skips_unpartitioned_tables() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    assert_eq!(store.create_partitions(7).await.unwrap(), 0);
+    assert_eq!(store.drop_expired_partitions().await.unwrap(), 0);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn bulk_import_replaces_sessions_in_other_partitions() {
+    let db = TestDb::new().await;
+    let store = partitioned_store(&db).await;
+    store.create_partitions(3).await.unwrap();
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    record.expiry_date = OffsetDateTime::now_utc() + Duration::days(2);
+    store.bulk_import(tokio_stream::iter([record.clone()])).await.unwrap();
+
+    assert_eq!(db.count_rows("session").await, 1);
+    assert_loads(&store, &record).await;
+}
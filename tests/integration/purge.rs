@@ -0,0 +1,98 @@
+use sea_orm::{ColumnTrait, Condition};
+use time::Duration;
+use tower_sessions::session::Record;
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::entity::session;
+use tower_sessions_seaorm_store::{user_id_key, PostgresStore, PurgeFilter};
+
+use crate::helpers::{active_record, assert_loads, assert_missing, expired_record, TestDb};
+
+/// Creates a session of user `user_id` with `store`.
+async fn create_session_of(store: &PostgresStore, mut record: Record, user_id: &str) -> Record {
+    record.data.insert("user_id".to_string(), user_id.into());
+    store.create(&mut record).await.unwrap();
+    record
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn purge_where_removes_every_session_of_the_users() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_user_id_extractor(user_id_key("user_id"));
+    let alice = create_session_of(&store, active_record(), "alice").await;
+    create_session_of(&store, expired_record(), "alice").await;
+    let bob = create_session_of(&store, active_record(), "bob").await;
+    let carol = create_session_of(&store, active_record(), "carol").await;
+
+    let purged = store
+        .purge_where(PurgeFilter::UserIds(vec!["alice".to_string(), "bob".to_string()]))
+        .await
+        .unwrap();
+
+    // Expired sessions are purged along with active ones
+    assert_eq!(purged, 3);
+    assert_missing(&store, &alice.id).await;
+    assert_missing(&store, &bob.id).await;
+    assert_loads(&store, &carol).await;
+    assert_eq!(db.count_rows("session").await, 1);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn purge_where_removes_sessions_matching_a_condition() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_user_id_extractor(user_id_key("user_id"));
+    let guest = create_session_of(&store, active_record(), "guest-1").await;
+    let member = create_session_of(&store, active_record(), "member-1").await;
+
+    let purged = store
+        .purge_where(Condition::all().add(session::Column::UserId.starts_with("guest-")))
+        .await
+        .unwrap();
+
+    assert_eq!(purged, 1);
+    assert_missing(&store, &guest.id).await;
+    assert_loads(&store, &member).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn purge_where_removes_rows_in_soft_delete_mode() {
+    let db = TestDb::migrated().await;
+    let store = db
+        .store()
+        .with_user_id_extractor(user_id_key("user_id"))
+        .with_soft_delete(Duration::days(30));
+    let deleted = create_session_of(&store, active_record(), "alice").await;
+    create_session_of(&store, active_record(), "alice").await;
+    store.delete(&deleted.id).await.unwrap();
+
+    let purged = store.purge_where(PurgeFilter::UserIds(vec!["alice".to_string()])).await.unwrap();
+
+    // Soft-deleted sessions hold the data of the user too
+    assert_eq!(purged, 2);
+    assert_eq!(db.count_rows("session").await, 0);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn purge_where_without_user_ids_removes_nothing() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    store.create(&mut active_record()).await.unwrap();
+
+    assert_eq!(store.purge_where(PurgeFilter::UserIds(Vec::new())).await.unwrap(), 0);
+    assert_eq!(db.count_rows("session").await, 1);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn purge_where_fails_on_read_only_stores() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    store.create(&mut active_record()).await.unwrap();
+    store.set_read_only(true);
+
+    assert!(store.purge_where(Condition::all()).await.is_err());
+    assert_eq!(db.count_rows("session").await, 1);
+}
@@ -0,0 +1,47 @@
+use serde_json::json;
+use tower_sessions::SessionStore;
+
+use crate::helpers::{active_record, TestDb};
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn raw_query_counts_sessions() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    for _ in 0..3 {
+        store.create(&mut active_record()).await.unwrap();
+    }
+
+    let rows = store
+        .raw_query("SELECT COUNT(*) AS count FROM tower_sessions.session", Vec::new())
+        .await
+        .unwrap();
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["count"], 3);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn raw_query_binds_parameters() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    store.create(&mut active_record()).await.unwrap();
+
+    let rows = store
+        .raw_query("SELECT $1::text AS value", vec!["it's $1".into()])
+        .await
+        .unwrap();
+    assert_eq!(rows, [json!({ "value": "it's $1" })]);
+
+    let rows = store
+        .raw_query(
+            "SELECT id FROM tower_sessions.session WHERE id = $1",
+            vec![record.id.to_string().into()],
+        )
+        .await
+        .unwrap();
+    assert_eq!(rows, [json!({ "id": record.id.to_string() })]);
+}
@@ -0,0 +1,76 @@
+use sea_orm::DatabaseConnection;
+use tower_sessions::session::Id;
+use tower_sessions::{session_store, ExpiredDeletion, SessionStore};
+use tower_sessions_seaorm_store::{ConditionalSaveExpr, PostgresStore};
+
+use crate::helpers::{active_record, assert_loads, assert_missing, expired_record, TestDb};
+
+fn is_read_only_error<T>(result: session_store::Result<T>) -> bool {
+    matches!(result, Err(session_store::Error::Backend(message)) if message == "store is in read-only mode")
+}
+
+#[tokio::test]
+async fn read_only_store_rejects_writes_before_reaching_the_database() {
+    let store = PostgresStore::new(DatabaseConnection::Disconnected).with_read_only(true);
+    assert!(store.is_read_only());
+
+    // The disconnected store would panic if any of these reached the database
+    let mut record = active_record();
+    assert!(is_read_only_error(store.create(&mut record).await));
+    assert!(is_read_only_error(store.save(&record).await));
+    assert!(is_read_only_error(store.delete(&record.id).await));
+    assert!(is_read_only_error(
+        store.copy_session(&record.id, &Id::default()).await.map_err(Into::into)
+    ));
+    assert!(is_read_only_error(
+        store.conditional_save(&record, ConditionalSaveExpr::OnlyIfExists).await
+    ));
+    assert!(is_read_only_error(
+        store.upsert_raw(&record.id, vec![0x80], record.expiry_date).await
+    ));
+
+    // Deleting expired sessions is skipped instead
+    store.delete_expired().await.unwrap();
+    assert_eq!(store.delete_expired_count().await.unwrap(), 0);
+    store.delete_expired_with_lock().await.unwrap();
+}
+
+#[test]
+fn read_only_mode_is_shared_between_clones() {
+    let store = PostgresStore::new(DatabaseConnection::Disconnected);
+    let clone = store.clone();
+    assert!(!clone.is_read_only());
+
+    store.set_read_only(true);
+    assert!(clone.is_read_only());
+
+    clone.set_read_only(false);
+    assert!(!store.is_read_only());
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn read_only_store_serves_reads() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut active = active_record();
+    let mut expired = expired_record();
+    store.create(&mut active).await.unwrap();
+    store.create(&mut expired).await.unwrap();
+
+    store.set_read_only(true);
+    assert_loads(&store, &active).await;
+    assert_eq!(store.count_active().await.unwrap(), 1);
+
+    // Expired sessions are left in place until writes are allowed again
+    store.delete_expired().await.unwrap();
+    assert_eq!(db.count_rows("session").await, 2);
+
+    store.set_read_only(false);
+    store.delete_expired().await.unwrap();
+    assert_eq!(db.count_rows("session").await, 1);
+
+    store.delete(&active.id).await.unwrap();
+    assert_missing(&store, &active.id).await;
+}
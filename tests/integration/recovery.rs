@@ -0,0 +1,50 @@
+use tower_sessions::session::Record;
+use tower_sessions::{session_store, SessionStore};
+use tower_sessions_seaorm_store::RecoveryStrategy;
+
+use crate::helpers::{active_record, TestDb};
+
+/// MessagePack tag followed by a byte MessagePack never uses.
+const CORRUPT_DATA: [u8; 2] = [0xC3, 0xC1];
+
+/// Creates a session, then overwrites its data with `CORRUPT_DATA`.
+async fn corrupt_session(db: &TestDb) -> Record {
+    let mut record = active_record();
+    db.store().create(&mut record).await.unwrap();
+    db.set_stored_data(&record.id.to_string(), CORRUPT_DATA.to_vec()).await;
+    record
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn corrupt_sessions_fail_to_load_by_default() {
+    let db = TestDb::migrated().await;
+    let record = corrupt_session(&db).await;
+    let store = db.store().with_serialize_error_recovery(RecoveryStrategy::Fail);
+
+    assert!(matches!(store.load(&record.id).await, Err(session_store::Error::Decode(_))));
+    assert!(matches!(db.store().load(&record.id).await, Err(session_store::Error::Decode(_))));
+    assert_eq!(db.count_rows("session").await, 1);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn return_none_keeps_corrupt_sessions() {
+    let db = TestDb::migrated().await;
+    let record = corrupt_session(&db).await;
+    let store = db.store().with_serialize_error_recovery(RecoveryStrategy::ReturnNone);
+
+    assert_eq!(store.load(&record.id).await.unwrap(), None);
+    assert_eq!(db.stored_data(&record.id.to_string()).await, CORRUPT_DATA);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_and_return_none_removes_corrupt_sessions() {
+    let db = TestDb::migrated().await;
+    let record = corrupt_session(&db).await;
+    let store = db.store().with_serialize_error_recovery(RecoveryStrategy::DeleteAndReturnNone);
+
+    assert_eq!(store.load(&record.id).await.unwrap(), None);
+    assert_eq!(db.count_rows("session").await, 0);
+}
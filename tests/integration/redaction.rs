@@ -0,0 +1,93 @@
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+use serde_json::json;
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::{LoggingTelemetryProvider, RedactAllValues, RedactFn, RedactKeyList};
+use tracing_subscriber::fmt::MakeWriter;
+
+use crate::helpers::{active_record, assert_loads, TestDb};
+
+/// Collects the output of a `tracing` subscriber.
+#[derive(Clone, Default)]
+struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+impl CapturedLogs {
+    fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for CapturedLogs {
+    type Writer = Self;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+#[test]
+fn redact_all_values_keeps_the_shape_of_the_data() {
+    let redact: RedactFn = RedactAllValues.into();
+
+    assert_eq!(
+        redact(json!({ "user_id": 42, "tags": ["a", { "b": null }], "admin": true })),
+        json!({ "user_id": "<redacted>", "tags": ["<redacted>", { "b": "<redacted>" }], "admin": "<redacted>" }),
+    );
+}
+
+#[test]
+fn redact_key_list_only_redacts_listed_keys() {
+    let redact: RedactFn = RedactKeyList(vec!["email".to_string(), "phone".to_string()]).into();
+
+    assert_eq!(
+        redact(json!({
+            "email": "jane@example.com",
+            "theme": "dark",
+            "contacts": [{ "phone": "555-0100", "name": "John" }],
+        })),
+        json!({
+            "email": "<redacted>",
+            "theme": "dark",
+            "contacts": [{ "phone": "<redacted>", "name": "John" }],
+        }),
+    );
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn logged_session_data_is_redacted_but_stored_data_is_not() {
+    let db = TestDb::migrated().await;
+    let store = db
+        .store()
+        .with_telemetry_provider(Arc::new(LoggingTelemetryProvider))
+        .with_session_data_redaction(RedactKeyList(vec!["email".to_string()]).into());
+
+    let logs = CapturedLogs::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::TRACE)
+        .with_writer(logs.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let mut record = active_record();
+    record.data.insert("email".to_string(), "jane@example.com".into());
+    store.create(&mut record).await.unwrap();
+    assert_loads(&store, &record).await;
+
+    let logs = logs.contents();
+    assert!(logs.contains("<redacted>"), "redacted data was not logged: {logs}");
+    assert!(logs.contains("dark"), "unredacted keys were not logged: {logs}");
+    assert!(!logs.contains("jane@example.com"), "email was logged: {logs}");
+}
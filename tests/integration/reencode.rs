@@ -0,0 +1,126 @@
+use tokio_stream::StreamExt;
+use tower_sessions::session::Record;
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::{PayloadCodec, PayloadVersion, PostgresStore, ReencodeProgress, SessionDataFormat};
+
+use crate::helpers::{active_record, assert_loads, TestDb};
+
+/// Runs `reencode_all` on `store` to the end, returning the progress of every batch.
+async fn reencode(store: &PostgresStore, from: PayloadCodec, to: PayloadCodec, batch_size: u64) -> Vec<ReencodeProgress> {
+    store
+        .reencode_all(from, to, batch_size)
+        .collect::<Result<Vec<_>, _>>()
+        .await
+        .unwrap()
+}
+
+/// Creates `count` sessions with `store`.
+async fn create_sessions(store: &PostgresStore, count: usize) -> Vec<Record> {
+    let mut records = Vec::new();
+    for _ in 0..count {
+        let mut record = active_record();
+        store.create(&mut record).await.unwrap();
+        records.push(record);
+    }
+    records
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn reencode_all_converts_message_pack_to_json_in_batches() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let records = create_sessions(&store, 3).await;
+
+    let progress = reencode(&store, PayloadCodec::MessagePack, PayloadCodec::Json, 2).await;
+
+    assert_eq!(
+        progress,
+        [
+            ReencodeProgress { scanned: 2, reencoded: 2, failed: 0 },
+            ReencodeProgress { scanned: 3, reencoded: 3, failed: 0 },
+        ]
+    );
+    for record in &records {
+        assert_loads(&store, record).await;
+    }
+
+    // JSON data converts to JSONB in SQL
+    let store = store.with_data_format(SessionDataFormat::Jsonb);
+    store.migrate().await.unwrap();
+    for record in &records {
+        assert_loads(&store, record).await;
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn reencode_all_skips_sessions_in_other_codecs() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    create_sessions(&store, 2).await;
+
+    let progress = reencode(&store, PayloadCodec::Json, PayloadCodec::MessagePack, 10).await;
+
+    assert_eq!(progress, [ReencodeProgress { scanned: 2, reencoded: 0, failed: 0 }]);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn reencode_all_tags_untagged_sessions() {
+    let db = TestDb::migrated().await;
+    let untagged = db.store().with_payload_version(PayloadVersion::Untagged);
+    let records = create_sessions(&untagged, 1).await;
+    create_sessions(&db.store(), 1).await;
+
+    let progress = reencode(&db.store(), PayloadCodec::MessagePack, PayloadCodec::MessagePack, 10).await;
+
+    // Only the untagged session changes
+    assert_eq!(progress, [ReencodeProgress { scanned: 2, reencoded: 1, failed: 0 }]);
+    assert_loads(&untagged, &records[0]).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn reencode_all_counts_sessions_that_cant_be_decoded() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let record = active_record();
+    store.upsert_raw(&record.id, vec![0xC3, 0xFF], record.expiry_date).await.unwrap();
+
+    let progress = reencode(&store, PayloadCodec::MessagePack, PayloadCodec::Json, 10).await;
+
+    assert_eq!(progress, [ReencodeProgress { scanned: 1, reencoded: 0, failed: 1 }]);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn reencode_all_fails_on_read_only_stores() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    store.set_read_only(true);
+
+    let result = store
+        .reencode_all(PayloadCodec::MessagePack, PayloadCodec::Json, 10)
+        .collect::<Vec<_>>()
+        .await;
+
+    assert!(matches!(result.as_slice(), [Err(_)]), "{result:?}");
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn reencode_all_only_converts_sessions_of_the_tenant() {
+    let db = TestDb::migrated().await;
+    let acme = db.store().for_tenant("acme");
+    let globex = db.store().for_tenant("globex");
+    create_sessions(&acme, 2).await;
+    create_sessions(&globex, 1).await;
+
+    let progress = reencode(&acme, PayloadCodec::MessagePack, PayloadCodec::Json, 10).await;
+    assert_eq!(progress, [ReencodeProgress { scanned: 2, reencoded: 2, failed: 0 }]);
+
+    // The session of the other tenant is still MessagePack
+    let progress = reencode(&globex, PayloadCodec::MessagePack, PayloadCodec::Json, 10).await;
+    assert_eq!(progress, [ReencodeProgress { scanned: 1, reencoded: 1, failed: 0 }]);
+}
@@ -0,0 +1,52 @@
+use tower_sessions::SessionStore;
+
+use crate::helpers::{active_record, assert_loads, TestDb};
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn loads_are_spread_over_the_replicas() {
+    let primary = TestDb::migrated().await;
+    let replicas = [TestDb::migrated().await, TestDb::migrated().await];
+    let store = primary
+        .store()
+        .with_read_replicas(replicas.iter().map(|replica| replica.conn.clone()).collect());
+
+    // Each database holds its own version of the session
+    let mut versions = Vec::new();
+    let record = active_record();
+    for (db, theme) in [(&primary, "primary"), (&replicas[0], "first"), (&replicas[1], "second")] {
+        let mut version = record.clone();
+        version.data.insert("theme".to_string(), theme.into());
+        db.store().save(&version).await.unwrap();
+        versions.push(version);
+    }
+
+    let mut themes = Vec::new();
+    for _ in 0..4 {
+        let loaded = store.load(&record.id).await.unwrap().unwrap();
+        themes.push(loaded.data["theme"].clone());
+    }
+    assert_eq!(themes, ["first", "second", "first", "second"]);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn writes_go_to_the_primary() {
+    let primary = TestDb::migrated().await;
+    let replica = TestDb::migrated().await;
+    let store = primary.store().with_read_replicas(vec![replica.conn.clone()]);
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    store.save(&record).await.unwrap();
+    assert_eq!(primary.count_rows("session").await, 1);
+    assert_eq!(replica.count_rows("session").await, 0);
+
+    // The session hasn't reached the replica, so it is loaded from the primary
+    assert_loads(&store, &record).await;
+
+    replica.store().save(&record).await.unwrap();
+    store.delete(&record.id).await.unwrap();
+    assert_eq!(primary.count_rows("session").await, 0);
+    assert_eq!(replica.count_rows("session").await, 1);
+}
@@ -0,0 +1,81 @@
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+use time::{Duration, OffsetDateTime};
+use tower_sessions::session::Record;
+use tower_sessions::session_store;
+
+use crate::helpers::{active_record, expired_record, TestDb};
+
+/// Adds an entry for `record` to the audit log, as a trigger on the session table would.
+async fn log_operation(db: &TestDb, record: &Record, operation: &str, occurred_at: OffsetDateTime) {
+    let statement = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"INSERT INTO "tower_sessions"."session_audit_log" (session_id, operation, occurred_at, data)
+           VALUES ($1, $2, $3, $4)"#,
+        [
+            record.id.to_string().into(),
+            operation.into(),
+            occurred_at.into(),
+            rmp_serde::to_vec(record).unwrap().into(),
+        ],
+    );
+
+    db.conn.execute(statement).await.expect("failed to write the audit log");
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn restore_is_not_available_on_standard_postgres() {
+    let db = TestDb::migrated().await;
+
+    let result = db
+        .store()
+        .restore_from_point_in_time(OffsetDateTime::now_utc())
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(session_store::Error::Backend(message)) if message == "point-in-time restore not available"
+    ));
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn restore_reconstructs_sessions_from_the_audit_log() {
+    let db = TestDb::migrated().await;
+    // With the `audit-log` feature, the migrations already created the table without data
+    db.conn
+        .execute_unprepared(
+            r#"CREATE TABLE IF NOT EXISTS "tower_sessions"."session_audit_log" (
+                id BIGSERIAL PRIMARY KEY,
+                session_id TEXT NOT NULL,
+                operation TEXT NOT NULL,
+                occurred_at TIMESTAMPTZ NOT NULL
+            );
+            ALTER TABLE "tower_sessions"."session_audit_log" ADD COLUMN IF NOT EXISTS data BYTEA"#,
+        )
+        .await
+        .unwrap();
+
+    let as_of = OffsetDateTime::now_utc() - Duration::hours(1);
+    let before = as_of - Duration::minutes(10);
+    let after = as_of + Duration::minutes(10);
+
+    // Saved before and after the restore point: restored with its earlier data
+    let mut changed = active_record();
+    log_operation(&db, &changed, "create", before).await;
+    let restored = changed.clone();
+    changed.data.insert("theme".to_string(), "light".into());
+    log_operation(&db, &changed, "save", after).await;
+
+    // Deleted before the restore point
+    let deleted = active_record();
+    log_operation(&db, &deleted, "create", before).await;
+    log_operation(&db, &deleted, "delete", before + Duration::minutes(1)).await;
+
+    // Created after the restore point, or already expired at that time
+    log_operation(&db, &active_record(), "create", after).await;
+    log_operation(&db, &expired_record(), "create", before).await;
+
+    let records = db.store().restore_from_point_in_time(as_of).await.unwrap();
+    assert_eq!(records, [restored]);
+}
@@ -0,0 +1,59 @@
+use std::time::{Duration, Instant};
+
+use sea_orm::{ConnectOptions, Database, DatabaseConnection, TransactionTrait};
+use tower_sessions::{session_store, SessionStore};
+use tower_sessions_seaorm_store::{PostgresStore, RetryPolicy};
+
+use crate::helpers::{active_record, assert_loads, TestDb};
+
+const POLICY: RetryPolicy = RetryPolicy {
+    max_retries: 5,
+    initial_backoff: Duration::from_millis(50),
+    max_backoff: Duration::from_millis(100),
+};
+
+/// Returns a pool with a single connection that gives up waiting for it quickly.
+async fn single_connection(db: &TestDb) -> DatabaseConnection {
+    let mut options = ConnectOptions::new(db.url.clone());
+    options.max_connections(1).acquire_timeout(Duration::from_millis(100));
+    Database::connect(options).await.unwrap()
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn operations_are_retried_until_a_connection_is_available() {
+    let db = TestDb::migrated().await;
+    let conn = single_connection(&db).await;
+    let store = PostgresStore::new(conn.clone());
+    let retrying = store.clone().with_retry(POLICY);
+    let mut record = active_record();
+    retrying.create(&mut record).await.unwrap();
+
+    // Hold the only connection for a while
+    let txn = conn.begin().await.unwrap();
+    let holder = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        txn.commit().await.unwrap();
+    });
+
+    // Without retries the pool timeout is returned right away
+    assert!(matches!(store.load(&record.id).await, Err(session_store::Error::Backend(_))));
+    assert_loads(&retrying, &record).await;
+    holder.await.unwrap();
+}
+
+#[tokio::test]
+async fn retries_stop_once_the_policy_is_exhausted() {
+    // Nothing listens on port 1, so every attempt fails
+    let mut options = ConnectOptions::new("postgres://postgres@127.0.0.1:1/sessions");
+    options.connect_lazy(true).acquire_timeout(Duration::from_millis(100));
+    let store = PostgresStore::new(Database::connect(options).await.unwrap()).with_retry(RetryPolicy {
+        max_retries: 2,
+        ..POLICY
+    });
+
+    let started = Instant::now();
+    assert!(store.load(&active_record().id).await.is_err());
+    // Two waits of 50 and 100 milliseconds
+    assert!(started.elapsed() >= Duration::from_millis(150));
+}
@@ -0,0 +1,118 @@
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, Statement};
+use time::Duration;
+use tower_sessions::session::Id;
+use tower_sessions::{ExpiredDeletion, SessionStore};
+use tower_sessions_seaorm_store::PostgresStore;
+
+use crate::helpers::{active_record, assert_loads, assert_missing, expired_record, TestDb};
+
+/// Returns whether session `id` is marked as deleted in the default session table.
+async fn is_soft_deleted(db: &TestDb, id: &Id) -> bool {
+    db.conn
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"SELECT deleted_at IS NOT NULL AS deleted FROM "tower_sessions"."session" WHERE id = $1"#,
+            [id.to_string().into()],
+        ))
+        .await
+        .unwrap()
+        .expect("session not found")
+        .try_get("", "deleted")
+        .unwrap()
+}
+
+/// Moves the deletion time of session `id` back by `age`.
+async fn age_deletion(db: &TestDb, id: &Id, age: Duration) {
+    db.conn
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"UPDATE "tower_sessions"."session" SET deleted_at = deleted_at - make_interval(secs => $1) WHERE id = $2"#,
+            [(age.whole_seconds() as f64).into(), id.to_string().into()],
+        ))
+        .await
+        .unwrap();
+}
+
+#[test]
+fn gc_policy_reports_the_soft_delete_retention() {
+    let store = PostgresStore::new(DatabaseConnection::Disconnected).with_soft_delete(Duration::days(30));
+
+    assert_eq!(store.gc_policy().soft_delete_retention, Some(Duration::days(30)));
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_marks_sessions_instead_of_removing_them() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_soft_delete(Duration::days(30));
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    store.delete(&record.id).await.unwrap();
+
+    assert_missing(&store, &record.id).await;
+    assert!(is_soft_deleted(&db, &record.id).await);
+    assert_eq!(store.count_active().await.unwrap(), 0);
+    assert_eq!(db.count_rows("session").await, 1);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn saves_do_not_restore_soft_deleted_sessions() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_soft_delete(Duration::days(30));
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    store.delete(&record.id).await.unwrap();
+
+    record.data.insert("theme".to_string(), "light".into());
+    store.save(&record).await.unwrap();
+
+    assert_missing(&store, &record.id).await;
+    assert!(is_soft_deleted(&db, &record.id).await);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_expired_keeps_soft_deleted_sessions_for_the_retention() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_soft_delete(Duration::days(30));
+    let mut recent = expired_record();
+    let mut old = active_record();
+    store.create(&mut recent).await.unwrap();
+    store.create(&mut old).await.unwrap();
+    store.delete(&recent.id).await.unwrap();
+    store.delete(&old.id).await.unwrap();
+    age_deletion(&db, &old.id, Duration::days(31)).await;
+
+    assert_eq!(store.count_expired().await.unwrap(), 1);
+    store.delete_expired().await.unwrap();
+
+    // The expired session was deleted recently, so it is kept for review
+    assert_eq!(db.count_rows("session").await, 1);
+    assert!(is_soft_deleted(&db, &recent.id).await);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn purge_deleted_removes_sessions_past_the_retention() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_soft_delete(Duration::days(30));
+    let mut live = active_record();
+    let mut recent = active_record();
+    let mut old = active_record();
+    for record in [&mut live, &mut recent, &mut old] {
+        store.create(record).await.unwrap();
+    }
+    store.delete(&recent.id).await.unwrap();
+    store.delete(&old.id).await.unwrap();
+    age_deletion(&db, &old.id, Duration::days(31)).await;
+
+    assert_eq!(store.purge_deleted().await.unwrap(), 1);
+    assert_loads(&store, &live).await;
+    assert!(is_soft_deleted(&db, &recent.id).await);
+
+    // Without soft delete, every soft-deleted session is purged
+    assert_eq!(db.store().purge_deleted().await.unwrap(), 1);
+    assert_eq!(db.count_rows("session").await, 1);
+}
@@ -0,0 +1,64 @@
+use sea_orm::{ConnectionTrait, DatabaseConnection};
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::PostgresStore;
+
+use crate::helpers::{active_record, assert_loads, assert_missing, expired_record, TestDb};
+
+/// Returns a `tower-sessions-sqlx-store` store on the database of `db`, using `table_name`.
+async fn sqlx_store(db: &TestDb, table_name: &str) -> tower_sessions_sqlx_store::PostgresStore {
+    let store = tower_sessions_sqlx_store::PostgresStore::new(db.conn.get_postgres_connection_pool().clone())
+        .with_table_name(table_name)
+        .unwrap();
+    store.migrate().await.unwrap();
+    store
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn import_sqlx_store_copies_active_sessions() {
+    let db = TestDb::migrated().await;
+    let sqlx_store = sqlx_store(&db, "sqlx_sessions").await;
+
+    let mut active = active_record();
+    let mut expired = expired_record();
+    sqlx_store.create(&mut active).await.unwrap();
+    sqlx_store.create(&mut expired).await.unwrap();
+    db.conn
+        .execute_unprepared(r#"INSERT INTO "tower_sessions"."sqlx_sessions" VALUES ('garbage', '\x00', now() + interval '1 day')"#)
+        .await
+        .unwrap();
+
+    let store = db.store();
+    assert_eq!(store.import_sqlx_store("tower_sessions", "sqlx_sessions").await.unwrap(), 1);
+    assert_loads(&store, &active).await;
+    assert_missing(&store, &expired.id).await;
+    assert_eq!(db.count_rows("sqlx_sessions").await, 3);
+
+    // Sessions imported before are skipped
+    assert_eq!(store.import_sqlx_store("tower_sessions", "sqlx_sessions").await.unwrap(), 0);
+    assert_eq!(db.count_rows("session").await, 1);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn migrate_adapts_sqlx_store_table_in_place() {
+    let db = TestDb::new().await;
+    let sqlx_store = sqlx_store(&db, "session").await;
+    let mut record = active_record();
+    sqlx_store.create(&mut record).await.unwrap();
+
+    let store = db.store();
+    store.migrate().await.unwrap();
+
+    assert_loads(&store, &record).await;
+    record.data.insert("theme".to_string(), "light".into());
+    store.save(&record).await.unwrap();
+    assert_loads(&store, &record).await;
+}
+
+#[tokio::test]
+async fn import_sqlx_store_rejects_invalid_table_name() {
+    let store = PostgresStore::new(DatabaseConnection::Disconnected);
+
+    assert!(store.import_sqlx_store("tower_sessions", "session; DROP TABLE x").await.is_err());
+}
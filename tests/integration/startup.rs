@@ -0,0 +1,88 @@
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+use tower_sessions::{session_store, SessionStore};
+use tower_sessions_seaorm_store::{SeaOrmStoreError, StartupCheck};
+
+use crate::helpers::{active_record, assert_loads, TestDb};
+
+/// Returns the number of migrations recorded as applied.
+async fn applied_migrations(db: &TestDb) -> i64 {
+    let statement = Statement::from_string(
+        DbBackend::Postgres,
+        "SELECT COUNT(*) AS count FROM tower_sessions_seaorm_migrations".to_string(),
+    );
+
+    db.conn
+        .query_one(statement)
+        .await
+        .expect("failed to count migrations")
+        .expect("COUNT returned no rows")
+        .try_get("", "count")
+        .expect("failed to read the migration count")
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn migrate_if_needed_migrates_on_first_use_only() {
+    let db = TestDb::new().await;
+    let store = db.store().with_startup_check(StartupCheck::MigrateIfNeeded);
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    assert_loads(&store, &record).await;
+    assert!(applied_migrations(&db).await > 0);
+
+    // The check doesn't run again: with the table gone, operations hit the database error
+    db.conn
+        .execute_unprepared(r#"DROP TABLE "tower_sessions"."session"; DELETE FROM tower_sessions_seaorm_migrations"#)
+        .await
+        .unwrap();
+    assert!(store.clone().load(&record.id).await.is_err());
+    assert_eq!(applied_migrations(&db).await, 0);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn failed_startup_check_is_cached() {
+    let db = TestDb::new().await;
+    let store = db.store().with_startup_check(StartupCheck::FailIfMigrationsNeeded);
+
+    let result = store.load(&active_record().id).await;
+    assert!(matches!(result, Err(session_store::Error::Backend(message)) if message.starts_with("startup check failed")));
+
+    // Fixing the schema afterwards doesn't help, the failure is kept
+    store.migrate().await.unwrap();
+    let result = store.create(&mut active_record()).await;
+    assert!(matches!(result, Err(session_store::Error::Backend(message)) if message.contains("pending migrations")));
+
+    // A store configured after the migration passes the check
+    let store = db.store().with_startup_check(StartupCheck::FailIfMigrationsNeeded);
+    store.create(&mut active_record()).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn verify_schema_detects_missing_tables_and_columns() {
+    let db = TestDb::migrated().await;
+
+    db.store().verify_schema().await.unwrap();
+    db.store()
+        .with_startup_check(StartupCheck::VerifySchema)
+        .count_active()
+        .await
+        .unwrap();
+
+    let missing_table = db.store().with_table_name("missing").unwrap();
+    assert!(matches!(
+        missing_table.verify_schema().await,
+        Err(SeaOrmStoreError::InvalidSchema(message)) if message.contains("does not exist")
+    ));
+
+    db.conn
+        .execute_unprepared(r#"ALTER TABLE "tower_sessions"."session" DROP COLUMN version"#)
+        .await
+        .unwrap();
+    assert!(matches!(
+        db.store().verify_schema().await,
+        Err(SeaOrmStoreError::InvalidSchema(message)) if message.ends_with("missing columns version")
+    ));
+}
@@ -0,0 +1,89 @@
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::{SessionDataFormat, StoreStats};
+
+use crate::helpers::{active_record, expired_record, TestDb};
+
+/// Returns the sum of `size` over the sessions in the default session table.
+async fn stored_payload_bytes(db: &TestDb, size: &str) -> u64 {
+    let bytes: i64 = db
+        .conn
+        .query_one(Statement::from_string(
+            DbBackend::Postgres,
+            format!(r#"SELECT SUM({size})::bigint AS bytes FROM "tower_sessions"."session""#),
+        ))
+        .await
+        .unwrap()
+        .unwrap()
+        .try_get("", "bytes")
+        .unwrap();
+    bytes.unsigned_abs()
+}
+
+#[test]
+fn stats_display_a_summary() {
+    let stats = StoreStats {
+        total_sessions: 3,
+        active_sessions: 2,
+        expired_sessions: 1,
+        total_payload_bytes: 100,
+        average_payload_bytes: 100.0 / 3.0,
+    };
+
+    assert_eq!(
+        stats.to_string(),
+        "3 sessions (2 active, 1 expired), payload: 100 bytes, 33.3 bytes on average"
+    );
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn stats_of_an_empty_table_are_zero() {
+    let db = TestDb::migrated().await;
+
+    assert_eq!(db.store().stats().await.unwrap(), StoreStats::default());
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn stats_count_sessions_and_payload_bytes() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    store.create(&mut active_record()).await.unwrap();
+    store.create(&mut active_record()).await.unwrap();
+    store.create(&mut expired_record()).await.unwrap();
+
+    let stats = store.stats().await.unwrap();
+
+    assert_eq!(stats.total_sessions, 3);
+    assert_eq!(stats.active_sessions, 2);
+    assert_eq!(stats.expired_sessions, 1);
+    assert_eq!(stats.total_payload_bytes, stored_payload_bytes(&db, "OCTET_LENGTH(data)").await);
+    assert_eq!(stats.average_payload_bytes, stats.total_payload_bytes as f64 / 3.0);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn stats_measure_jsonb_payloads() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_data_format(SessionDataFormat::Jsonb);
+    store.migrate().await.unwrap();
+    store.create(&mut active_record()).await.unwrap();
+
+    let stats = store.stats().await.unwrap();
+
+    assert_eq!(stats.total_sessions, 1);
+    assert_eq!(stats.total_payload_bytes, stored_payload_bytes(&db, "OCTET_LENGTH(data::text)").await);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn stats_of_a_tenant_only_count_its_sessions() {
+    let db = TestDb::migrated().await;
+    db.store().for_tenant("acme").create(&mut active_record()).await.unwrap();
+    db.store().for_tenant("globex").create(&mut expired_record()).await.unwrap();
+
+    let stats = db.store().for_tenant("acme").stats().await.unwrap();
+
+    assert_eq!((stats.total_sessions, stats.active_sessions, stats.expired_sessions), (1, 1, 0));
+}
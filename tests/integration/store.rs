@@ -0,0 +1,334 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use time::OffsetDateTime;
+use tower_sessions::session_store::Error;
+use tower_sessions::{ExpiredDeletion, SessionStore};
+use tower_sessions_seaorm_store::PostgresStore;
+
+use crate::helpers::{active_record, assert_loads, assert_missing, expired_record, TestDb};
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn create_then_load_returns_same_record() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    assert_loads(&store, &record).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_makes_load_return_none() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    store.delete(&record.id).await.unwrap();
+
+    assert_missing(&store, &record.id).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn save_updates_data_on_reload() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    record.data.insert("theme".to_string(), "light".into());
+    store.save(&record).await.unwrap();
+
+    assert_loads(&store, &record).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn save_inserts_missing_session() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let record = active_record();
+    store.save(&record).await.unwrap();
+
+    assert_loads(&store, &record).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn concurrent_saves_of_a_new_session_all_succeed() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let record = active_record();
+
+    let saves: Vec<_> = (0..20)
+        .map(|_| {
+            let store = store.clone();
+            let record = record.clone();
+            tokio::spawn(async move { store.save(&record).await })
+        })
+        .collect();
+    for save in saves {
+        save.await.unwrap().unwrap();
+    }
+
+    // One save inserted the session, every other one updated it
+    assert_loads(&store, &record).await;
+    assert_eq!(store.session_version(&record.id).await.unwrap(), Some(19));
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn save_takes_a_single_statement() {
+    let db = TestDb::migrated().await;
+    let statements = Arc::new(Mutex::new(Vec::new()));
+    let mut conn = db.conn.clone();
+    {
+        let statements = statements.clone();
+        conn.set_metric_callback(move |info| statements.lock().unwrap().push(info.statement.sql.clone()));
+    }
+    let store = PostgresStore::new(conn);
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    statements.lock().unwrap().clear();
+
+    // No lookup precedes the upsert, whether or not the session exists
+    store.save(&record).await.unwrap();
+    store.save(&active_record()).await.unwrap();
+    let session_statements: Vec<_> = statements
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|sql| sql.contains(r#"."session" "#))
+        .cloned()
+        .collect();
+    assert_eq!(session_statements.len(), 2, "{session_statements:?}");
+    assert!(session_statements.iter().all(|sql| sql.starts_with("INSERT")), "{session_statements:?}");
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn concurrent_creates_with_the_same_id_get_distinct_ids() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let record = active_record();
+
+    let creates: Vec<_> = (0..4)
+        .map(|_| {
+            let store = store.clone();
+            let mut record = record.clone();
+            tokio::spawn(async move { store.create(&mut record).await.map(|()| record.id) })
+        })
+        .collect();
+    let mut ids = HashSet::new();
+    for create in creates {
+        ids.insert(create.await.unwrap().unwrap());
+    }
+
+    // One create kept the ID, the others lost the race on the primary key and took new ones
+    assert_eq!(ids.len(), 4);
+    assert!(ids.contains(&record.id));
+    assert_eq!(db.count_rows("session").await, 4);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn load_ignores_expired_session() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut record = expired_record();
+    store.create(&mut record).await.unwrap();
+
+    assert_missing(&store, &record.id).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_expired_removes_only_expired_sessions() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut active = active_record();
+    let mut expired = expired_record();
+    store.create(&mut active).await.unwrap();
+    store.create(&mut expired).await.unwrap();
+
+    store.delete_expired().await.unwrap();
+
+    assert_eq!(db.count_rows("session").await, 1);
+    assert_loads(&store, &active).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_expired_count_returns_number_of_deleted_sessions() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    for mut record in [active_record(), expired_record(), expired_record()] {
+        store.create(&mut record).await.unwrap();
+    }
+
+    assert_eq!(store.delete_expired_count().await.unwrap(), 2);
+    assert_eq!(store.delete_expired_count().await.unwrap(), 0);
+    assert_eq!(db.count_rows("session").await, 1);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_expired_batched_deletes_all_batches() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut active = active_record();
+    store.create(&mut active).await.unwrap();
+    for _ in 0..5 {
+        store.create(&mut expired_record()).await.unwrap();
+    }
+
+    assert_eq!(store.delete_expired_batched(2).await.unwrap(), 5);
+    assert_eq!(db.count_rows("session").await, 1);
+    assert_loads(&store, &active).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn count_active_ignores_expired_sessions() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    assert_eq!(store.count_active().await.unwrap(), 0);
+
+    for mut record in [active_record(), active_record(), expired_record()] {
+        store.create(&mut record).await.unwrap();
+    }
+
+    assert_eq!(store.count_active().await.unwrap(), 2);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn count_expired_counts_sessions_waiting_for_cleanup() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    for mut record in [active_record(), expired_record(), expired_record()] {
+        store.create(&mut record).await.unwrap();
+    }
+
+    assert_eq!(store.count_expired().await.unwrap(), 2);
+    store.delete_expired().await.unwrap();
+    assert_eq!(store.count_expired().await.unwrap(), 0);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn concurrent_create_returns_unique_ids_in_order() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let records: Vec<_> = (0..1000)
+        .map(|index| {
+            let mut record = active_record();
+            record.data.insert("index".to_string(), index.into());
+            record
+        })
+        .collect();
+
+    let ids = store.concurrent_create(records).await.unwrap();
+
+    assert_eq!(ids.len(), 1000);
+    assert_eq!(ids.iter().collect::<HashSet<_>>().len(), 1000);
+    assert_eq!(db.count_rows("session").await, 1000);
+
+    for (index, id) in ids.iter().enumerate().step_by(100) {
+        let record = store.load(id).await.unwrap().unwrap();
+        assert_eq!(record.data["index"], index);
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn upsert_raw_stores_bytes_as_is() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut record = active_record();
+    let raw_bytes = rmp_serde::to_vec(&record).unwrap();
+    store
+        .upsert_raw(&record.id, raw_bytes, record.expiry_date)
+        .await
+        .unwrap();
+    assert_loads(&store, &record).await;
+
+    // Upserting again overwrites the existing session
+    record.data.insert("theme".to_string(), "light".into());
+    let raw_bytes = rmp_serde::to_vec(&record).unwrap();
+    store
+        .upsert_raw(&record.id, raw_bytes, record.expiry_date)
+        .await
+        .unwrap();
+    assert_loads(&store, &record).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn upsert_raw_rejects_empty_data() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let record = active_record();
+    let result = store
+        .upsert_raw(&record.id, Vec::new(), OffsetDateTime::now_utc())
+        .await;
+
+    assert!(matches!(result, Err(Error::Encode(_))));
+    assert_eq!(db.count_rows("session").await, 0);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn per_key_size_limit_rejects_large_value() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_data_size_limit_per_key(1024);
+
+    let mut record = active_record();
+    record.data.insert("cart".to_string(), "x".repeat(2048).into());
+
+    let result = store.create(&mut record).await;
+    assert!(
+        matches!(&result, Err(Error::Encode(message)) if message.contains("'cart'")),
+        "unexpected result: {result:?}"
+    );
+
+    let result = store.save(&record).await;
+    assert!(matches!(result, Err(Error::Encode(_))));
+    assert_eq!(db.count_rows("session").await, 0);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn per_key_size_limit_accepts_many_small_values() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_data_size_limit_per_key(1024);
+
+    // Every value is below the limit, but the session as a whole is much larger
+    let mut record = active_record();
+    for index in 0..32 {
+        record.data.insert(format!("key_{index}"), "x".repeat(512).into());
+    }
+
+    store.create(&mut record).await.unwrap();
+    record.data.insert("key_0".to_string(), "y".repeat(512).into());
+    store.save(&record).await.unwrap();
+
+    assert_loads(&store, &record).await;
+}
@@ -0,0 +1,73 @@
+use std::collections::HashSet;
+
+use time::{Duration, OffsetDateTime};
+use tokio_stream::StreamExt;
+use tower_sessions::session::Id;
+use tower_sessions::{session_store, SessionStore};
+
+use crate::helpers::{active_record, expired_record, TestDb};
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn stream_active_yields_every_active_session_across_pages() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_stream_page_size(2);
+
+    let mut expected = HashSet::new();
+    for _ in 0..5 {
+        let mut record = active_record();
+        store.create(&mut record).await.unwrap();
+        expected.insert(record.id);
+    }
+    for _ in 0..2 {
+        store.create(&mut expired_record()).await.unwrap();
+    }
+
+    let records: Vec<_> = store.stream_active().collect().await;
+    let ids: HashSet<_> = records.into_iter().map(|record| record.unwrap().id).collect();
+    assert_eq!(ids, expected);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn stream_active_ends_immediately_without_sessions() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut stream = std::pin::pin!(store.stream_active());
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn undecodable_session_yields_an_error_item() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_stream_page_size(1);
+
+    for _ in 0..2 {
+        store.create(&mut active_record()).await.unwrap();
+    }
+    // 0xc1 is never used by MessagePack
+    store
+        .upsert_raw(&Id::default(), vec![0xc1], OffsetDateTime::now_utc() + Duration::days(1))
+        .await
+        .unwrap();
+
+    let results: Vec<_> = store.stream_active().collect().await;
+    assert_eq!(results.len(), 3);
+    assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 2);
+    assert!(results
+        .iter()
+        .any(|result| matches!(result, Err(session_store::Error::Decode(_)))));
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn database_error_ends_the_stream() {
+    let db = TestDb::new().await;
+    // Without migrations the session table doesn't exist
+    let store = db.store();
+
+    let results: Vec<_> = store.stream_active().collect().await;
+    assert!(matches!(results.as_slice(), [Err(session_store::Error::Backend(_))]));
+}
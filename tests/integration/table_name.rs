@@ -0,0 +1,226 @@
+use sea_orm::{ConnectionTrait, DatabaseConnection};
+use tower_sessions::{ExpiredDeletion, SessionStore};
+use tower_sessions_seaorm_store::{PostgresStore, SeaOrmStoreError};
+
+use crate::helpers::{active_record, assert_loads, assert_missing, expired_record, TestDb};
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn with_table_name_uses_specified_table() {
+    let db = TestDb::migrated().await;
+    db.create_session_table("custom_sessions").await;
+    let store = db.store().with_table_name("custom_sessions").unwrap();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    assert_eq!(db.count_rows("custom_sessions").await, 1);
+    assert_eq!(db.count_rows("session").await, 0);
+    assert_loads(&store, &record).await;
+    assert_eq!(store.count_active().await.unwrap(), 1);
+
+    store.delete(&record.id).await.unwrap();
+    assert_eq!(db.count_rows("custom_sessions").await, 0);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn clone_with_table_leaves_original_store_unaffected() {
+    let db = TestDb::migrated().await;
+    db.create_session_table("tenant_a_sessions").await;
+    let base_store = db.store();
+    let tenant_store = base_store.clone_with_table("tenant_a_sessions").unwrap();
+
+    assert_eq!(base_store.table_name(), "session");
+    assert_eq!(tenant_store.table_name(), "tenant_a_sessions");
+
+    let mut tenant_record = active_record();
+    tenant_store.create(&mut tenant_record).await.unwrap();
+    let mut base_record = active_record();
+    base_store.create(&mut base_record).await.unwrap();
+
+    assert_loads(&tenant_store, &tenant_record).await;
+    assert_missing(&base_store, &tenant_record.id).await;
+    assert_loads(&base_store, &base_record).await;
+    assert_missing(&tenant_store, &base_record.id).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn stores_with_different_tables_do_not_collide() {
+    let db = TestDb::migrated().await;
+    db.create_session_table("sessions_a").await;
+    db.create_session_table("sessions_b").await;
+    let store_a = db.store().with_table_name("sessions_a").unwrap();
+    let store_b = db.store().with_table_name("sessions_b").unwrap();
+
+    // The same session ID holds different data in each table
+    let record_a = active_record();
+    let mut record_b = record_a.clone();
+    record_b.data.insert("theme".to_string(), "light".into());
+    store_a.save(&record_a).await.unwrap();
+    store_b.save(&record_b).await.unwrap();
+    assert_loads(&store_a, &record_a).await;
+    assert_loads(&store_b, &record_b).await;
+
+    let mut expired = expired_record();
+    store_a.create(&mut expired).await.unwrap();
+    store_b.save(&expired).await.unwrap();
+    store_b.delete_expired().await.unwrap();
+    assert_eq!(db.count_rows("sessions_a").await, 2);
+    assert_eq!(db.count_rows("sessions_b").await, 1);
+
+    store_a.delete(&record_a.id).await.unwrap();
+    assert_missing(&store_a, &record_a.id).await;
+    assert_loads(&store_b, &record_b).await;
+    assert_eq!(db.count_rows("session").await, 0);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn with_schema_name_uses_specified_schema() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_schema_name("myapp").unwrap();
+    store.migrate().await.unwrap();
+    db.conn
+        .execute_unprepared(
+            r#"CREATE TABLE "myapp"."app_session" (LIKE "tower_sessions"."session" INCLUDING ALL)"#,
+        )
+        .await
+        .unwrap();
+    let store = store.with_table_name("app_session").unwrap();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    assert_loads(&store, &record).await;
+    assert_eq!(db.count_rows("session").await, 0);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn migrate_creates_table_in_configured_schema() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_schema_name("public").unwrap();
+    store.migrate().await.unwrap();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    assert_loads(&store, &record).await;
+    assert_eq!(db.count_rows("session").await, 0);
+    let rows = db
+        .conn
+        .query_one(sea_orm::Statement::from_string(
+            db.conn.get_database_backend(),
+            r#"SELECT COUNT(*) AS count FROM "public"."session""#,
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(rows.try_get::<i64>("", "count").unwrap(), 1);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn migrate_creates_missing_schema() {
+    let db = TestDb::new().await;
+    let store = db.store().with_schema_name("myapp").unwrap();
+    store.migrate().await.unwrap();
+    // Migrating again neither recreates the schema nor reruns the migrations
+    store.migrate().await.unwrap();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    assert_loads(&store, &record).await;
+    let default_schema = db
+        .conn
+        .query_one(sea_orm::Statement::from_string(
+            db.conn.get_database_backend(),
+            "SELECT 1 FROM pg_namespace WHERE nspname = 'tower_sessions'",
+        ))
+        .await
+        .unwrap();
+    assert!(default_schema.is_none());
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn migrate_creates_table_with_configured_name() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_table_name("prod_sessions").unwrap();
+    store.migrate().await.unwrap();
+    // Migrating again doesn't rerun the migrations of the table
+    store.migrate().await.unwrap();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    assert_loads(&store, &record).await;
+    assert_eq!(db.count_rows("prod_sessions").await, 1);
+    assert_eq!(db.count_rows("session").await, 0);
+    let indexes = db
+        .conn
+        .query_one(sea_orm::Statement::from_string(
+            db.conn.get_database_backend(),
+            "SELECT COUNT(*) AS count FROM pg_indexes \
+             WHERE schemaname = 'tower_sessions' AND tablename = 'prod_sessions'",
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+    // The primary key and the indexes on expiry_date, last_accessed_at, user_id, created_at,
+    // tenant_id, deleted_at and, with client metadata, ip_address
+    let expected = if cfg!(feature = "client-metadata") { 8 } else { 7 };
+    assert_eq!(indexes.try_get::<i64>("", "count").unwrap(), expected);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn migrate_down_drops_only_the_configured_table() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_table_name("prod_sessions").unwrap();
+    store.migrate().await.unwrap();
+
+    store.migrate_down(None).await.unwrap();
+
+    let mut record = active_record();
+    assert!(store.create(&mut record).await.is_err());
+    db.store().create(&mut record).await.unwrap();
+    assert_loads(&db.store(), &record).await;
+}
+
+#[test]
+fn with_table_prefix_prepends_to_default_table_name() {
+    let store = PostgresStore::new(DatabaseConnection::Disconnected)
+        .with_table_name("custom_sessions")
+        .unwrap()
+        .with_table_prefix("prod_")
+        .unwrap();
+    assert_eq!(store.table_name(), "prod_session");
+
+    let store = store.with_schema_name("myapp").unwrap();
+    assert_eq!((store.schema_name(), store.table_name()), ("myapp", "prod_session"));
+}
+
+#[test]
+fn with_table_suffix_appends_to_default_table_name() {
+    let store = PostgresStore::new(DatabaseConnection::Disconnected)
+        .with_table_suffix("_v2")
+        .unwrap();
+    assert_eq!(store.table_name(), "session_v2");
+}
+
+#[test]
+fn table_prefix_and_suffix_are_validated() {
+    let store = PostgresStore::new(DatabaseConnection::Disconnected);
+    assert!(matches!(
+        store.clone().with_table_prefix("prod-"),
+        Err(SeaOrmStoreError::InvalidIdentifier(name)) if name == "prod-session"
+    ));
+    assert!(matches!(
+        store.with_table_suffix("; DROP TABLE users"),
+        Err(SeaOrmStoreError::InvalidIdentifier(_))
+    ));
+}
@@ -0,0 +1,44 @@
+use tower_sessions::SessionStore;
+
+use crate::helpers::{active_record, expired_record, TestDb};
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn list_tables_returns_tables_matching_the_pattern() {
+    let db = TestDb::migrated().await;
+    db.create_session_table("session_tenant_b").await;
+    db.create_session_table("session_tenant_a").await;
+    db.create_session_table("users").await;
+
+    let tables = db.store().list_tables().await.unwrap();
+    assert_eq!(tables, ["session", "session_tenant_a", "session_tenant_b"]);
+
+    let tables = db
+        .store()
+        .with_table_name_pattern("session\\_tenant%")
+        .list_tables()
+        .await
+        .unwrap();
+    assert_eq!(tables, ["session_tenant_a", "session_tenant_b"]);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn count_tables_with_sessions_ignores_expired_sessions() {
+    let db = TestDb::migrated().await;
+    db.create_session_table("session_tenant_a").await;
+    db.create_session_table("session_tenant_b").await;
+    db.create_session_table("session_tenant_c").await;
+
+    let store = db.store();
+    assert_eq!(store.count_tables_with_sessions().await.unwrap(), 0);
+
+    let tenant_a = store.clone_with_table("session_tenant_a").unwrap();
+    tenant_a.create(&mut active_record()).await.unwrap();
+    tenant_a.create(&mut expired_record()).await.unwrap();
+    let tenant_b = store.clone_with_table("session_tenant_b").unwrap();
+    tenant_b.create(&mut expired_record()).await.unwrap();
+    store.create(&mut active_record()).await.unwrap();
+
+    assert_eq!(store.count_tables_with_sessions().await.unwrap(), 2);
+}
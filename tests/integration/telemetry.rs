@@ -0,0 +1,88 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tower_sessions::{ExpiredDeletion, SessionStore};
+use tower_sessions_seaorm_store::{PostgresStore, StoreOperation, TelemetryProvider};
+
+use crate::helpers::{active_record, TestDb};
+
+/// Records every measurement it receives.
+#[derive(Default)]
+struct MockTelemetryProvider {
+    operations: Mutex<Vec<(StoreOperation, bool)>>,
+    session_sizes: Mutex<Vec<usize>>,
+}
+
+impl MockTelemetryProvider {
+    fn take_operations(&self) -> Vec<(StoreOperation, bool)> {
+        std::mem::take(&mut *self.operations.lock().unwrap())
+    }
+}
+
+impl TelemetryProvider for MockTelemetryProvider {
+    fn record_operation(&self, op: StoreOperation, _duration: Duration, success: bool) {
+        self.operations.lock().unwrap().push((op, success));
+    }
+
+    fn record_session_size(&self, bytes: usize) {
+        self.session_sizes.lock().unwrap().push(bytes);
+    }
+}
+
+fn instrumented_store(db: &TestDb) -> (PostgresStore, Arc<MockTelemetryProvider>) {
+    let provider = Arc::new(MockTelemetryProvider::default());
+    let store = db.store().with_telemetry_provider(provider.clone());
+    (store, provider)
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn every_operation_is_recorded_once() {
+    let db = TestDb::migrated().await;
+    let (store, provider) = instrumented_store(&db);
+    let mut record = active_record();
+
+    store.create(&mut record).await.unwrap();
+    assert_eq!(provider.take_operations(), [(StoreOperation::Create, true)]);
+
+    store.save(&record).await.unwrap();
+    assert_eq!(provider.take_operations(), [(StoreOperation::Save, true)]);
+
+    store.load(&record.id).await.unwrap();
+    assert_eq!(provider.take_operations(), [(StoreOperation::Load, true)]);
+
+    store.delete(&record.id).await.unwrap();
+    assert_eq!(provider.take_operations(), [(StoreOperation::Delete, true)]);
+
+    store.delete_expired().await.unwrap();
+    assert_eq!(provider.take_operations(), [(StoreOperation::DeleteExpired, true)]);
+
+    store.delete_expired_with_lock().await.unwrap();
+    assert_eq!(provider.take_operations(), [(StoreOperation::DeleteExpired, true)]);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn failed_operation_is_recorded_as_failure() {
+    let db = TestDb::new().await;
+    // Without migrations the session table doesn't exist
+    let (store, provider) = instrumented_store(&db);
+
+    assert!(store.load(&active_record().id).await.is_err());
+    assert_eq!(provider.take_operations(), [(StoreOperation::Load, false)]);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn session_sizes_are_recorded_on_write_and_read() {
+    let db = TestDb::migrated().await;
+    let (store, provider) = instrumented_store(&db);
+    let mut record = active_record();
+    // The MessagePack record behind its one-byte tag
+    let encoded_size = rmp_serde::to_vec(&record).unwrap().len() + 1;
+
+    store.create(&mut record).await.unwrap();
+    store.load(&record.id).await.unwrap();
+
+    assert_eq!(*provider.session_sizes.lock().unwrap(), [encoded_size, encoded_size]);
+}
@@ -0,0 +1,80 @@
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, Statement};
+use tower_sessions::{ExpiredDeletion, SessionStore};
+use tower_sessions_seaorm_store::PostgresStore;
+
+use crate::helpers::{active_record, assert_loads, assert_missing, expired_record, TestDb};
+
+/// Returns the tenant of session `id` as stored in the default session table.
+async fn stored_tenant_id(db: &TestDb, id: &str) -> Option<String> {
+    db.conn
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"SELECT tenant_id FROM "tower_sessions"."session" WHERE id = $1"#,
+            [id.into()],
+        ))
+        .await
+        .unwrap()
+        .expect("session not found")
+        .try_get("", "tenant_id")
+        .unwrap()
+}
+
+#[test]
+fn for_tenant_scopes_a_copy_of_the_store() {
+    let store = PostgresStore::new(DatabaseConnection::Disconnected);
+
+    assert_eq!(store.for_tenant("acme").tenant_id(), Some("acme"));
+    assert_eq!(store.tenant_id(), None);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn sessions_are_invisible_to_other_tenants() {
+    let db = TestDb::migrated().await;
+    let acme = db.store().for_tenant("acme");
+    let globex = db.store().for_tenant("globex");
+
+    let mut record = active_record();
+    acme.create(&mut record).await.unwrap();
+
+    assert_eq!(stored_tenant_id(&db, &record.id.to_string()).await.as_deref(), Some("acme"));
+    assert_loads(&acme, &record).await;
+    assert_missing(&globex, &record.id).await;
+    assert_eq!(globex.count_active().await.unwrap(), 0);
+    // A store without a tenant sees every tenant
+    assert_loads(&db.store(), &record).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn writes_leave_sessions_of_other_tenants_untouched() {
+    let db = TestDb::migrated().await;
+    let acme = db.store().for_tenant("acme");
+    let globex = db.store().for_tenant("globex");
+    let mut record = active_record();
+    acme.create(&mut record).await.unwrap();
+
+    let mut hijacked = record.clone();
+    hijacked.data.insert("user_id".to_string(), 7.into());
+    globex.save(&hijacked).await.unwrap();
+    globex.delete(&record.id).await.unwrap();
+
+    assert_loads(&acme, &record).await;
+    assert_eq!(db.count_rows("session").await, 1);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn delete_expired_only_deletes_sessions_of_the_tenant() {
+    let db = TestDb::migrated().await;
+    let acme = db.store().for_tenant("acme");
+    let globex = db.store().for_tenant("globex");
+    acme.create(&mut expired_record()).await.unwrap();
+    globex.create(&mut expired_record()).await.unwrap();
+
+    acme.delete_expired().await.unwrap();
+    assert_eq!(db.count_rows("session").await, 1);
+
+    db.store().delete_expired().await.unwrap();
+    assert_eq!(db.count_rows("session").await, 0);
+}
@@ -0,0 +1,54 @@
+use sea_orm::DatabaseConnection;
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::{SeaOrmStoreError, TenantStoreFactory};
+
+use crate::helpers::{active_record, assert_loads, assert_missing, TestDb};
+
+#[test]
+fn schema_name_prepends_the_prefix() {
+    let factory = TenantStoreFactory::new(DatabaseConnection::Disconnected).with_schema_prefix("tenant_");
+
+    assert_eq!(factory.schema_name("acme"), "tenant_acme");
+}
+
+#[tokio::test]
+async fn store_rejects_invalid_tenant_ids() {
+    let factory = TenantStoreFactory::new(DatabaseConnection::Disconnected);
+
+    let result = factory.store("acme; DROP SCHEMA public").await;
+
+    assert!(matches!(result, Err(SeaOrmStoreError::InvalidIdentifier(_))), "{result:?}");
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn stores_of_tenants_use_separate_schemas() {
+    let db = TestDb::new().await;
+    let factory = TenantStoreFactory::new(db.conn.clone()).with_schema_prefix("tenant_");
+
+    let acme = factory.store("acme").await.unwrap();
+    let globex = factory.store("globex").await.unwrap();
+    assert_eq!(acme.schema_name(), "tenant_acme");
+
+    let mut record = active_record();
+    acme.create(&mut record).await.unwrap();
+    assert_loads(&acme, &record).await;
+    assert_missing(&globex, &record.id).await;
+    acme.verify_schema().await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn store_migrates_a_schema_once() {
+    let db = TestDb::new().await;
+    let factory = TenantStoreFactory::new(db.conn.clone());
+    let clone = factory.clone();
+
+    let (first, second) = tokio::join!(factory.store("acme"), clone.store("acme"));
+    let mut record = active_record();
+    first.unwrap().create(&mut record).await.unwrap();
+
+    let store = factory.store("acme").await.unwrap();
+    assert_loads(&second.unwrap(), &record).await;
+    assert_loads(&store, &record).await;
+}
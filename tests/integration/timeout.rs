@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use sea_orm::{ConnectionTrait, TransactionTrait};
+use tower_sessions::{session_store, ExpiredDeletion, SessionStore};
+use tower_sessions_seaorm_store::OperationTimeouts;
+
+use crate::helpers::{active_record, assert_loads, TestDb};
+
+const TIMEOUT: Duration = Duration::from_millis(200);
+
+fn is_timeout<T>(result: session_store::Result<T>) -> bool {
+    matches!(result, Err(session_store::Error::Backend(message)) if message == "operation timeout")
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn every_operation_times_out_while_the_table_is_locked() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_per_operation_timeout(OperationTimeouts {
+        create: Some(TIMEOUT),
+        save: Some(TIMEOUT),
+        load: Some(TIMEOUT),
+        delete: Some(TIMEOUT),
+        delete_expired: Some(TIMEOUT),
+    });
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    // Hold an exclusive lock so every query against the table blocks until it is released
+    let lock = db.conn.begin().await.unwrap();
+    lock.execute_unprepared(r#"LOCK TABLE "tower_sessions"."session" IN ACCESS EXCLUSIVE MODE"#)
+        .await
+        .unwrap();
+
+    assert!(is_timeout(store.create(&mut active_record()).await));
+    assert!(is_timeout(store.save(&record).await));
+    assert!(is_timeout(store.upsert_raw(&record.id, vec![0x80], record.expiry_date).await));
+    assert!(is_timeout(store.load(&record.id).await));
+    assert!(is_timeout(store.delete(&record.id).await));
+    assert!(is_timeout(store.delete_expired().await));
+
+    lock.rollback().await.unwrap();
+    assert_loads(&store, &record).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn timeouts_only_apply_to_configured_operations() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_per_operation_timeout(OperationTimeouts {
+        load: Some(TIMEOUT),
+        ..Default::default()
+    });
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    let lock = db.conn.begin().await.unwrap();
+    lock.execute_unprepared(r#"LOCK TABLE "tower_sessions"."session" IN ACCESS EXCLUSIVE MODE"#)
+        .await
+        .unwrap();
+
+    assert!(is_timeout(store.load(&record.id).await));
+
+    // Without a limit, delete waits for the lock to be released
+    let delete = tokio::spawn({
+        let store = store.clone();
+        async move { store.delete(&record.id).await }
+    });
+    tokio::time::sleep(TIMEOUT * 2).await;
+    assert!(!delete.is_finished());
+
+    lock.rollback().await.unwrap();
+    delete.await.unwrap().unwrap();
+    assert!(store.load(&record.id).await.unwrap().is_none());
+}
@@ -0,0 +1,98 @@
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+use tower_sessions::session::Id;
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::user_id_key;
+
+use crate::helpers::{active_record, TestDb};
+
+/// Returns the `created_at` and `updated_at` columns of session `id`.
+async fn stored_timestamps(db: &TestDb, id: &Id) -> (Option<DateTimeWithTimeZone>, Option<DateTimeWithTimeZone>) {
+    let row = db
+        .conn
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"SELECT "created_at", "updated_at" FROM "tower_sessions"."session" WHERE "id" = $1"#,
+            [id.to_string().into()],
+        ))
+        .await
+        .unwrap()
+        .expect("session row not found");
+
+    (row.try_get("", "created_at").unwrap(), row.try_get("", "updated_at").unwrap())
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn create_sets_created_at_and_updated_at() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    let (created_at, updated_at) = stored_timestamps(&db, &record.id).await;
+    assert!(created_at.is_some());
+    assert_eq!(created_at, updated_at);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn save_refreshes_updated_at_only() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    let (created_at, _) = stored_timestamps(&db, &record.id).await;
+
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    record.data.insert("theme".to_string(), "light".into());
+    store.save(&record).await.unwrap();
+
+    let (saved_created_at, updated_at) = stored_timestamps(&db, &record.id).await;
+    assert_eq!(saved_created_at, created_at);
+    assert!(updated_at.unwrap() > created_at.unwrap());
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn save_keeps_unknown_created_at() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    // A session created before the columns were added
+    db.conn
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"UPDATE "tower_sessions"."session" SET "created_at" = NULL, "updated_at" = NULL WHERE "id" = $1"#,
+            [record.id.to_string().into()],
+        ))
+        .await
+        .unwrap();
+
+    store.save(&record).await.unwrap();
+
+    let (created_at, updated_at) = stored_timestamps(&db, &record.id).await;
+    assert_eq!(created_at, None);
+    assert!(updated_at.is_some());
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn sessions_for_user_reports_created_at() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_user_id_extractor(user_id_key("user_id"));
+
+    // The record holds `"user_id": 42`
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    let sessions = store.sessions_for_user("42", 0, 10).await.unwrap();
+    assert_eq!(sessions.len(), 1);
+    let created_at = sessions[0].created_at.expect("created_at not recorded");
+    assert!(created_at <= sessions[0].last_accessed_at);
+}
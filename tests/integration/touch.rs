@@ -0,0 +1,81 @@
+use time::{Duration, OffsetDateTime};
+use tower_sessions::session::Id;
+use tower_sessions::SessionStore;
+
+use crate::helpers::{active_record, assert_loads, assert_missing, expired_record, TestDb};
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn touch_moves_the_expiry_date_without_rewriting_the_data() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    let expiry_date = OffsetDateTime::now_utc() + Duration::days(7);
+    assert!(store.touch(&record.id, expiry_date).await.unwrap());
+
+    let loaded = store.load(&record.id).await.unwrap().expect("session not found");
+    assert_eq!(loaded.data, record.data);
+    assert!((loaded.expiry_date - expiry_date).abs() < Duration::microseconds(1), "{}", loaded.expiry_date);
+    // The data didn't change, so neither did the version
+    assert_eq!(store.session_version(&record.id).await.unwrap(), Some(0));
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn touch_can_shorten_sessions() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    assert!(store.touch(&record.id, OffsetDateTime::now_utc() - Duration::seconds(1)).await.unwrap());
+
+    assert_missing(&store, &record.id).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn touch_skips_missing_and_expired_sessions() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut expired = expired_record();
+    store.create(&mut expired).await.unwrap();
+    let expiry_date = OffsetDateTime::now_utc() + Duration::days(1);
+
+    assert!(!store.touch(&Id::default(), expiry_date).await.unwrap());
+    assert!(!store.touch(&expired.id, expiry_date).await.unwrap());
+
+    assert_missing(&store, &expired.id).await;
+    assert_eq!(db.count_rows("session").await, 1);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn touch_respects_the_max_lifetime() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_max_lifetime(Duration::days(1));
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    assert!(store.touch(&record.id, OffsetDateTime::now_utc() + Duration::days(7)).await.unwrap());
+
+    let expiry_date = store.load(&record.id).await.unwrap().expect("session not found").expiry_date;
+    assert!(expiry_date <= OffsetDateTime::now_utc() + Duration::days(1), "{expiry_date}");
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn saves_after_a_touch_write_the_expiry_date_of_the_record() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    store.touch(&record.id, OffsetDateTime::now_utc() + Duration::days(7)).await.unwrap();
+
+    record.data.insert("theme".to_string(), "light".into());
+    store.save(&record).await.unwrap();
+
+    assert_loads(&store, &record).await;
+}
@@ -0,0 +1,107 @@
+use sea_orm::{ConnectionTrait, TransactionTrait};
+use tower_sessions::SessionStore;
+
+use crate::helpers::{active_record, assert_loads, assert_missing, TestDb};
+
+/// Creates the table standing in for the application's own writes.
+async fn create_users_table(db: &TestDb) {
+    db.conn
+        .execute_unprepared(r#"CREATE TABLE "tower_sessions"."users" (name TEXT NOT NULL)"#)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn create_in_commits_with_the_application_writes() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    create_users_table(&db).await;
+
+    let txn = db.conn.begin().await.unwrap();
+    txn.execute_unprepared(r#"INSERT INTO "tower_sessions"."users" VALUES ('alice')"#)
+        .await
+        .unwrap();
+    let mut record = active_record();
+    store.create_in(&txn, &mut record).await.unwrap();
+
+    // Not visible outside the transaction before it commits
+    assert_missing(&store, &record.id).await;
+    txn.commit().await.unwrap();
+
+    assert_loads(&store, &record).await;
+    assert_eq!(db.count_rows("users").await, 1);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn rolled_back_transaction_leaves_sessions_untouched() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut kept = active_record();
+    let mut deleted = active_record();
+    store.create(&mut kept).await.unwrap();
+    store.create(&mut deleted).await.unwrap();
+
+    let txn = db.conn.begin().await.unwrap();
+    let mut created = active_record();
+    store.create_in(&txn, &mut created).await.unwrap();
+    let mut changed = kept.clone();
+    changed.data.insert("theme".to_string(), "light".into());
+    store.save_in(&txn, &changed).await.unwrap();
+    store.delete_in(&txn, &deleted.id).await.unwrap();
+    txn.rollback().await.unwrap();
+
+    assert_missing(&store, &created.id).await;
+    assert_loads(&store, &kept).await;
+    assert_loads(&store, &deleted).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn save_in_and_delete_in_apply_on_commit() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut saved = active_record();
+    let mut deleted = active_record();
+    store.create(&mut saved).await.unwrap();
+    store.create(&mut deleted).await.unwrap();
+
+    let txn = db.conn.begin().await.unwrap();
+    saved.data.insert("theme".to_string(), "light".into());
+    store.save_in(&txn, &saved).await.unwrap();
+    store.delete_in(&txn, &deleted.id).await.unwrap();
+    txn.commit().await.unwrap();
+
+    assert_loads(&store, &saved).await;
+    assert_missing(&store, &deleted.id).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn create_in_recovers_from_id_collision_without_aborting_the_transaction() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    create_users_table(&db).await;
+
+    let mut existing = active_record();
+    store.create(&mut existing).await.unwrap();
+
+    let txn = db.conn.begin().await.unwrap();
+    let mut colliding = active_record();
+    colliding.id = existing.id;
+    store.create_in(&txn, &mut colliding).await.unwrap();
+    assert_ne!(colliding.id, existing.id);
+
+    // The transaction is still usable after the collision
+    txn.execute_unprepared(r#"INSERT INTO "tower_sessions"."users" VALUES ('alice')"#)
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+
+    assert_loads(&store, &existing).await;
+    assert_loads(&store, &colliding).await;
+    assert_eq!(db.count_rows("users").await, 1);
+}
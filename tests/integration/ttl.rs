@@ -0,0 +1,52 @@
+use time::{Duration, OffsetDateTime};
+use tower_sessions::session::Record;
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::PostgresStore;
+
+use crate::helpers::{active_record, TestDb};
+
+const TTL: Duration = Duration::hours(1);
+
+/// Asserts that session `record` loads with an expiry date `TTL` after `start`, give or take
+/// the 100ms the call may have taken and the microsecond precision of the column.
+async fn assert_expires_after_ttl(store: &PostgresStore, record: &Record, start: OffsetDateTime) {
+    let expiry_date = store.load(&record.id).await.unwrap().expect("session not found").expiry_date;
+    let offset = expiry_date - (start + TTL);
+    assert!(offset > -Duration::microseconds(1) && offset < Duration::milliseconds(100), "{offset}");
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn create_with_ttl_expires_the_session_ttl_from_now() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut record = active_record();
+
+    let start = OffsetDateTime::now_utc();
+    store.create_with_ttl(&mut record, TTL).await.unwrap();
+
+    assert_expires_after_ttl(&store, &record, start).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn save_with_ttl_only_moves_the_expiry_date() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    let data = db.stored_data(&record.id.to_string()).await;
+
+    // Unsaved changes aren't written
+    let mut changed = record.clone();
+    changed.data.insert("theme".to_string(), "light".into());
+    let start = OffsetDateTime::now_utc();
+    assert!(store.save_with_ttl(&changed, TTL).await.unwrap());
+
+    assert_expires_after_ttl(&store, &record, start).await;
+    assert_eq!(db.stored_data(&record.id.to_string()).await, data);
+
+    // Missing sessions aren't created
+    assert!(!store.save_with_ttl(&active_record(), TTL).await.unwrap());
+    assert_eq!(db.count_rows("session").await, 1);
+}
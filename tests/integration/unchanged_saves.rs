@@ -0,0 +1,106 @@
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+use time::Duration;
+use tower_sessions::session::{Id, Record};
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::PostgresStore;
+
+use crate::helpers::{active_record, assert_loads, TestDb};
+
+/// Returns a store skipping saves that don't change the data, within a minute.
+fn skipping_store(db: &TestDb) -> PostgresStore {
+    db.store().with_skip_unchanged_saves(Duration::minutes(1))
+}
+
+/// Creates `record` with `store`, and returns it as loaded back.
+async fn create_and_load(store: &PostgresStore, record: &mut Record) -> Record {
+    store.create(record).await.unwrap();
+    store.load(&record.id).await.unwrap().expect("session not found")
+}
+
+/// Returns the version of session `id`, which every written save increments.
+async fn version(store: &PostgresStore, id: &Id) -> Option<u64> {
+    store.session_version(id).await.unwrap()
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn saves_that_dont_change_the_data_are_skipped() {
+    let db = TestDb::migrated().await;
+    let store = skipping_store(&db);
+    let mut record = active_record();
+    // The loaded data is an equal map, but may iterate in another order
+    let loaded = create_and_load(&store, &mut record).await;
+
+    store.save(&loaded).await.unwrap();
+
+    assert_eq!(version(&store, &record.id).await, Some(0));
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn saves_that_change_the_data_are_written() {
+    let db = TestDb::migrated().await;
+    let store = skipping_store(&db);
+    let mut record = active_record();
+    let mut loaded = create_and_load(&store, &mut record).await;
+
+    loaded.data.insert("theme".to_string(), "light".into());
+    store.save(&loaded).await.unwrap();
+
+    assert_eq!(version(&store, &record.id).await, Some(1));
+    assert_loads(&store, &loaded).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn saves_moving_the_expiry_date_past_the_tolerance_are_written() {
+    let db = TestDb::migrated().await;
+    let store = skipping_store(&db);
+    let mut record = active_record();
+    let mut loaded = create_and_load(&store, &mut record).await;
+
+    loaded.expiry_date += Duration::seconds(30);
+    store.save(&loaded).await.unwrap();
+    assert_eq!(version(&store, &record.id).await, Some(0));
+
+    loaded.expiry_date += Duration::minutes(5);
+    store.save(&loaded).await.unwrap();
+    assert_eq!(version(&store, &record.id).await, Some(1));
+    assert_loads(&store, &loaded).await;
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn saves_refresh_a_stale_last_access_time() {
+    let db = TestDb::migrated().await;
+    let store = skipping_store(&db);
+    let mut record = active_record();
+    let loaded = create_and_load(&store, &mut record).await;
+    db.conn
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"UPDATE "tower_sessions"."session" SET last_accessed_at = now() - interval '5 minutes' WHERE id = $1"#,
+            [record.id.to_string().into()],
+        ))
+        .await
+        .unwrap();
+
+    store.save(&loaded).await.unwrap();
+
+    assert_eq!(version(&store, &record.id).await, Some(1));
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn sessions_written_without_a_data_hash_are_saved_once() {
+    let db = TestDb::migrated().await;
+    let store = skipping_store(&db);
+    let mut record = active_record();
+    let loaded = create_and_load(&db.store(), &mut record).await;
+
+    store.save(&loaded).await.unwrap();
+    store.save(&loaded).await.unwrap();
+
+    // The first save stores the hash the second one matches
+    assert_eq!(version(&store, &record.id).await, Some(1));
+}
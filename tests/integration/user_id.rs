@@ -0,0 +1,105 @@
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+use tower_sessions::session::Id;
+use tower_sessions::SessionStore;
+use tower_sessions_seaorm_store::user_id_key;
+
+use crate::helpers::{active_record, expired_record, TestDb};
+
+/// Returns the `user_id` column of session `id`.
+async fn stored_user_id(db: &TestDb, id: &Id) -> Option<String> {
+    db.conn
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"SELECT "user_id" FROM "tower_sessions"."session" WHERE "id" = $1"#,
+            [id.to_string().into()],
+        ))
+        .await
+        .unwrap()
+        .expect("session row not found")
+        .try_get("", "user_id")
+        .unwrap()
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn user_id_is_extracted_on_create_and_save() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_user_id_extractor(user_id_key("user_id"));
+
+    // The record holds `"user_id": 42`
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+    assert_eq!(stored_user_id(&db, &record.id).await.as_deref(), Some("42"));
+
+    record.data.insert("user_id".to_string(), "alice".into());
+    store.save(&record).await.unwrap();
+    assert_eq!(stored_user_id(&db, &record.id).await.as_deref(), Some("alice"));
+
+    // Logging out clears the owner
+    record.data.remove("user_id");
+    store.save(&record).await.unwrap();
+    assert_eq!(stored_user_id(&db, &record.id).await, None);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn user_id_is_null_without_extractor() {
+    let db = TestDb::migrated().await;
+    let store = db.store();
+
+    let mut record = active_record();
+    store.create(&mut record).await.unwrap();
+
+    assert_eq!(stored_user_id(&db, &record.id).await, None);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker or DATABASE_URL"]
+async fn sessions_for_user_lists_active_sessions_by_page() {
+    let db = TestDb::migrated().await;
+    let store = db.store().with_user_id_extractor(user_id_key("user_id"));
+
+    // Records hold `"user_id": 42` unless changed
+    let mut sessions = [active_record(), active_record(), active_record()];
+    for record in &mut sessions {
+        store.create(record).await.unwrap();
+    }
+    let mut other_user = active_record();
+    other_user.data.insert("user_id".to_string(), 7.into());
+    store.create(&mut other_user).await.unwrap();
+    store.create(&mut expired_record()).await.unwrap();
+
+    // Saving moves the first session to the front
+    store.save(&sessions[0]).await.unwrap();
+
+    let first_page = store.sessions_for_user("42", 0, 2).await.unwrap();
+    let second_page = store.sessions_for_user("42", 1, 2).await.unwrap();
+    assert_eq!(first_page.len(), 2);
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(first_page[0].id, sessions[0].id);
+    assert_eq!(first_page[0].expiry_date.unix_timestamp(), sessions[0].expiry_date.unix_timestamp());
+    assert!(first_page[0].last_accessed_at >= first_page[1].last_accessed_at);
+
+    let mut listed: Vec<Id> = first_page.iter().chain(&second_page).map(|session| session.id).collect();
+    let mut expected: Vec<Id> = sessions.iter().map(|record| record.id).collect();
+    listed.sort_by_key(|id| id.0);
+    expected.sort_by_key(|id| id.0);
+    assert_eq!(listed, expected);
+
+    assert!(store.sessions_for_user("42", 2, 2).await.unwrap().is_empty());
+    assert!(store.sessions_for_user("unknown", 0, 2).await.unwrap().is_empty());
+}
+
+#[test]
+fn user_id_key_ignores_values_that_are_not_ids() {
+    let extract = user_id_key("user_id");
+    let mut record = active_record();
+    record.data.remove("user_id");
+    assert_eq!(extract(&record), None);
+
+    record.data.insert("user_id".to_string(), true.into());
+    assert_eq!(extract(&record), None);
+
+    record.data.insert("user_id".to_string(), "bob".into());
+    assert_eq!(extract(&record), Some("bob".to_string()));
+}
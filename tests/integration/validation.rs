@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use sea_orm::DatabaseConnection;
+use time::{Duration, OffsetDateTime};
+use tower_sessions::session::{Id, Record};
+use tower_sessions::{session_store, SessionStore};
+use tower_sessions_seaorm_store::{
+    default_table_validator, non_zero_id_validator, ConditionalSaveExpr, PostgresStore, SeaOrmStoreError,
+};
+
+// Validation happens while configuring the store, so no database is needed
+fn disconnected_store() -> PostgresStore {
+    PostgresStore::new(DatabaseConnection::Disconnected)
+}
+
+#[test]
+fn default_table_validator_accepts_plain_identifiers() {
+    for name in ["session", "tenant_a_sessions", "Sessions2", "_private"] {
+        assert!(default_table_validator(name), "{name:?} should be accepted");
+    }
+}
+
+#[test]
+fn default_table_validator_rejects_sql_metacharacters() {
+    for name in [
+        "",
+        "session\"",
+        "session; DROP TABLE users",
+        "session--",
+        "tower_sessions.session",
+        "my sessions",
+        "sessión",
+    ] {
+        assert!(!default_table_validator(name), "{name:?} should be rejected");
+    }
+}
+
+#[test]
+fn with_table_name_rejects_invalid_names() {
+    let result = disconnected_store().with_table_name("session\"; DROP TABLE users; --");
+    assert!(matches!(result, Err(SeaOrmStoreError::InvalidIdentifier(_))));
+
+    let result = disconnected_store().clone_with_table("tenant;a");
+    assert!(matches!(result, Err(SeaOrmStoreError::InvalidIdentifier(_))));
+}
+
+#[test]
+fn with_schema_name_rejects_invalid_names() {
+    let result = disconnected_store().with_schema_name("public\"");
+    assert!(matches!(result, Err(SeaOrmStoreError::InvalidIdentifier(_))));
+
+    let store = disconnected_store().with_schema_name("myapp").unwrap();
+    assert_eq!(store.schema_name(), "myapp");
+}
+
+#[test]
+fn custom_table_validator_replaces_default() {
+    let store = disconnected_store()
+        .with_table_validator(Arc::new(|name: &str| !name.contains(';')))
+        .unwrap()
+        .with_table_name("tenant-a")
+        .unwrap();
+    assert_eq!(store.table_name(), "tenant-a");
+
+    // The configured names are checked against the new validator too
+    let result = disconnected_store().with_table_validator(Arc::new(|name: &str| name.starts_with("tenant_")));
+    assert!(matches!(result, Err(SeaOrmStoreError::InvalidIdentifier(_))));
+}
+
+#[test]
+fn non_zero_id_validator_rejects_only_the_zero_id() {
+    assert!(!non_zero_id_validator(&Id(0)));
+    assert!(non_zero_id_validator(&Id::default()));
+}
+
+#[tokio::test]
+async fn session_id_validator_rejects_ids_before_reaching_the_database() {
+    let store = disconnected_store().with_session_id_validator(Arc::new(non_zero_id_validator));
+    let invalid_session_id = |result| {
+        matches!(result, Err(session_store::Error::Backend(message)) if message == "invalid session id")
+    };
+
+    // The disconnected store would panic if any of these reached the database
+    let mut record = Record {
+        id: Id(0),
+        data: Default::default(),
+        expiry_date: OffsetDateTime::now_utc() + Duration::days(1),
+    };
+    assert!(invalid_session_id(store.create(&mut record).await));
+    assert!(invalid_session_id(store.save(&record).await));
+    assert!(invalid_session_id(
+        store.conditional_save(&record, ConditionalSaveExpr::OnlyIfNotExists).await.map(|_| ())
+    ));
+    assert!(invalid_session_id(store.load(&record.id).await.map(|_| ())));
+    assert!(invalid_session_id(store.delete(&record.id).await));
+    assert!(invalid_session_id(
+        store.upsert_raw(&record.id, vec![0x80], record.expiry_date).await
+    ));
+    assert!(invalid_session_id(
+        store.copy_session(&Id::default(), &record.id).await.map_err(Into::into)
+    ));
+}
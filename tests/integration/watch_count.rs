@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+use futures_util::FutureExt;
+use sea_orm::DatabaseConnection;
+use tokio_stream::StreamExt;
+use tower_sessions_seaorm_store::PostgresStore;
+
+const INTERVAL: Duration = Duration::from_secs(10);
+
+// Counts of a closed store fail at once, so only the timer decides when items arrive
+#[tokio::test(start_paused = true)]
+async fn counts_are_emitted_every_interval() {
+    let store = PostgresStore::new(DatabaseConnection::Disconnected);
+    // There is no pool to close, but the store is closed regardless
+    let _ = store.close().await;
+    let mut counts = store.watch_count(INTERVAL);
+
+    // The first count is emitted immediately
+    assert!(counts.next().now_or_never().is_some());
+
+    for _ in 0..3 {
+        tokio::time::advance(INTERVAL - Duration::from_millis(1)).await;
+        assert!(counts.next().now_or_never().is_none());
+
+        tokio::time::advance(Duration::from_millis(1)).await;
+        assert!(counts.next().now_or_never().is_some());
+    }
+}